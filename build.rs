@@ -1,52 +1,88 @@
-use std::fs::{self, OpenOptions};
-use std::io::{self, Write};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process;
 
-fn main() -> io::Result<()> {
-    let days = get_days()?;
-    generate_days_rs(&days)?;
-    generate_toml(&days)?;
-    Ok(())
-}
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=Cargo.toml");
 
-fn get_days() -> io::Result<Vec<String>> {
-    Ok(fs::read_dir("./")?
-        .filter_map(Result::ok)
-        .filter(|entry| entry.file_name().to_str().unwrap().starts_with("day"))
-        .map(|entry| entry.file_name().to_string_lossy().into_owned())
-        .collect::<Vec<String>>())
+    if let Err(err) = register_days() {
+        eprintln!("error: {err}");
+        process::exit(1);
+    }
 }
 
-fn generate_days_rs(days: &[String]) -> io::Result<()> {
-    fs::copy("./src/reg_days_template", "./src/register_days.rs")?;
-    let mut file = OpenOptions::new()
-        .append(true)
-        .open("./src/register_days.rs")?;
-
-    let mut out = String::new();
-    days.iter().for_each(|day| {
-        out.push_str(&format!("    use {};\n", day));
-        out.push_str(&format!("    days.insert(\"{day}\", {day}::run);\n"));
-    });
+fn register_days() -> io::Result<()> {
+    let days = get_days()?;
+    check_dependencies(&days)?;
+    generate_days_rs(&days)
+}
 
-    writeln!(file, "{out}")?;
-    writeln!(file, "    days\n}}")?;
+/// Collects every `dayNN` directory in calendar order, rejecting
+/// directories that look like a day but are not named `day01`..`day25`.
+fn get_days() -> io::Result<Vec<String>> {
+    let mut days = vec![];
+    for entry in fs::read_dir(".")? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !name.starts_with("day") || !entry.file_type()?.is_dir() {
+            continue;
+        }
+        if !is_valid_day(&name) {
+            return Err(io::Error::other(format!(
+                "Malformed day directory `{name}`, expected `day01` to `day25`"
+            )));
+        }
+        days.push(name);
+    }
 
-    Ok(())
+    days.sort();
+    Ok(days)
 }
 
-fn generate_toml(days: &[String]) -> io::Result<()> {
-    fs::copy("Cargo_base.toml", "Cargo.toml")?;
-    let mut file = OpenOptions::new().append(true).open("Cargo.toml")?;
+fn is_valid_day(name: &str) -> bool {
+    let num = &name["day".len()..];
+    num.len() == 2
+        && num.bytes().all(|b| b.is_ascii_digit())
+        && (1..=25).contains(&num.parse::<u8>().unwrap())
+}
 
-    let days_as_deps = days
+fn check_dependencies(days: &[String]) -> io::Result<()> {
+    let manifest = fs::read_to_string("Cargo.toml")?;
+    let missing: Vec<_> = days
         .iter()
+        .filter(|day| {
+            !manifest
+                .lines()
+                .any(|line| line.split('=').next().map(str::trim) == Some(day.as_str()))
+        })
         .map(|day| format!("{day} = {{ path = \"{day}\" }}"))
-        .collect::<Vec<_>>()
-        .join("\n");
-    writeln!(file, "{days_as_deps}\n")?;
+        .collect();
 
-    let members = format!("[workspace]\nmembers = {:?}", days);
-    writeln!(file, "{members}\n")?;
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    Err(io::Error::other(format!(
+        "Day crates missing from the `[dependencies]` of Cargo.toml:\n{}",
+        missing.join("\n")
+    )))
+}
+
+fn generate_days_rs(days: &[String]) -> io::Result<()> {
+    let mut out = String::new();
+    out.push_str("pub fn register_days() -> DayMap {\n");
+    out.push_str("    let mut days: DayMap = BTreeMap::new();\n");
+    for day in days {
+        let solution = format!("D{}", &day[1..]);
+        out.push_str(&format!(
+            "    days.insert(\"{day}\", &{day}::{solution});\n"
+        ));
+    }
+    out.push_str("    days\n}\n");
 
-    Ok(())
+    let out_dir = env::var("OUT_DIR").map_err(io::Error::other)?;
+    fs::write(Path::new(&out_dir).join("register_days.rs"), out)
 }
@@ -1,17 +1,22 @@
 use itertools::Itertools;
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use shared::{parse_field, Answer, Error, Solution};
 
-pub fn run() -> io::Result<()> {
-    let (col1, col2) = parse_file("input")?;
+pub struct Day01;
 
-    let res_part1 = calculate_part1(&col1, &col2);
-    let res_part2 = calculate_part2(&col1, &col2);
+impl Solution for Day01 {
+    type Input = (Vec<i32>, Vec<i32>);
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1((col1, col2): &Self::Input) -> impl Answer {
+        calculate_part1(col1, col2)
+    }
 
-    Ok(())
+    fn part2((col1, col2): &Self::Input) -> impl Answer {
+        calculate_part2(col1, col2)
+    }
 }
 
 fn calculate_part1(col1: &[i32], col2: &[i32]) -> u64 {
@@ -36,63 +41,66 @@ fn calculate_part2(col1: &[i32], col2: &[i32]) -> usize {
 }
 
 fn dist(num1: i32, num2: i32) -> u64 {
-    (num1 as i64 - num2 as i64).abs() as u64
+    (num1 as i64 - num2 as i64).unsigned_abs()
 }
 
-fn parse_file(file_path: &str) -> io::Result<(Vec<i32>, Vec<i32>)> {
-    let file = File::open(file_path)?;
-    let reader = BufReader::new(file);
-
-    let res = reader
+fn parse_input(input: &str) -> shared::Result<(Vec<i32>, Vec<i32>)> {
+    input
         .lines()
-        .filter_map(|line| line.ok())
-        .map(|line| {
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
             let mut parts = line.split_whitespace();
-            let value1 = parts.next()?.parse::<i32>().ok()?;
-            let value2 = parts.next()?.parse::<i32>().ok()?;
-            Some((value1, value2))
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(value1), Some(value2), None) => Ok((
+                    parse_field::<i32>(idx + 1, line, value1)?,
+                    parse_field::<i32>(idx + 1, line, value2)?,
+                )),
+                _ => Err(Error::parse(idx + 1, 1, "expected two numbers")),
+            }
         })
-        .filter_map(|opt| opt)
-        .unzip();
-
-    Ok(res)
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    shared::sample_tests! {
+        Day01;
+        file "sample_input" {
+            sample_part1: part1 => 11,
+            sample_part2: part2 => 31,
+        }
+    }
+
     #[test]
     fn sample_file_parsing() {
-        let res = parse_file("sample_input");
+        let res = Day01::parse_file(shared::day_file!("sample_input"));
         assert!(res.is_ok());
         let (col1, col2) = res.unwrap();
         assert_eq!(col1, vec![3, 4, 2, 1, 3, 3]);
         assert_eq!(col2, vec![4, 3, 5, 3, 9, 3]);
     }
 
+    #[test]
+    fn malformed_line() {
+        let res = Day01::parse("3   4\n4   x3\n");
+        assert!(matches!(
+            res,
+            Err(Error::Parse {
+                line: 2,
+                column: 5,
+                ..
+            })
+        ));
+        assert!(Day01::parse("3   4   5\n").is_err());
+    }
+
     #[test]
     fn dist_fn_test() {
         assert_eq!(dist(3, 8), 5);
         assert_eq!(dist(10, -3), 13);
         assert_eq!(dist(i32::MAX, i32::MIN), i32::MAX as u64 * 2 + 1);
     }
-
-    #[test]
-    fn sample_part1() {
-        let res = parse_file("sample_input");
-        assert!(res.is_ok());
-        let (col1, col2) = res.unwrap();
-        let res = calculate_part1(&col1, &col2);
-        assert_eq!(res, 11);
-    }
-
-    #[test]
-    fn sample_part2() {
-        let res = parse_file("sample_input");
-        assert!(res.is_ok());
-        let (col1, col2) = res.unwrap();
-        let res = calculate_part2(&col1, &col2);
-        assert_eq!(res, 31);
-    }
 }
@@ -1,16 +1,21 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
-
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
-
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+use shared::{parse_field, Answer, Solution};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Vec<i16>>;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
 fn calculate_part1(input: &[Vec<i16>]) -> usize {
@@ -29,16 +34,14 @@ fn calculate_part2(input: &[Vec<i16>]) -> usize {
         .count()
 }
 
-fn parse_file(file_path: &str) -> io::Result<Vec<Vec<i16>>> {
-    File::open(file_path)
-        .map(io::BufReader::new)?
+fn parse_input(input: &str) -> shared::Result<Vec<Vec<i16>>> {
+    input
         .lines()
-        .map(|line| {
-            line.map(|l| {
-                l.split_whitespace()
-                    .filter_map(|num| num.parse::<i16>().ok())
-                    .collect()
-            })
+        .enumerate()
+        .map(|(idx, line)| {
+            line.split_whitespace()
+                .map(|num| parse_field(idx + 1, line, num))
+                .collect()
         })
         .collect()
 }
@@ -55,7 +58,7 @@ fn is_safe(report: &[i16]) -> bool {
         let (a, b) = (window[0], window[1]);
         let diff = a - b;
         let dist = diff.abs();
-        if dist < 1 || dist > 3 {
+        if !(1..=3).contains(&dist) {
             return false;
         }
 
@@ -98,9 +101,17 @@ fn is_safe_damped(report: &[i16]) -> bool {
 mod tests {
     use super::*;
 
+    shared::sample_tests! {
+        Day02;
+        file "sample_input" {
+            sample_part1: part1 => 2,
+            sample_part2: part2 => 4,
+        }
+    }
+
     #[test]
     fn parse_sample_input() {
-        let result = parse_file("sample_input");
+        let result = Day02::parse_file(shared::day_file!("sample_input"));
         assert!(result.is_ok());
         let result = result.unwrap();
         assert_eq!(
@@ -118,7 +129,7 @@ mod tests {
 
     #[test]
     fn is_safe_sampe_test() {
-        let input = parse_file("sample_input");
+        let input = Day02::parse_file(shared::day_file!("sample_input"));
         assert!(input.is_ok());
         let input = input.unwrap();
         let result: Vec<_> = input.iter().map(|report| is_safe(report)).collect();
@@ -127,7 +138,7 @@ mod tests {
 
     #[test]
     fn is_safe_damped_sampe_test() {
-        let input = parse_file("sample_input");
+        let input = Day02::parse_file(shared::day_file!("sample_input"));
         assert!(input.is_ok());
         let input = input.unwrap();
         let result: Vec<_> = input.iter().map(|report| is_safe_damped(report)).collect();
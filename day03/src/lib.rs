@@ -1,14 +1,22 @@
 use regex::Regex;
-use std::{fs, io};
+use shared::{Answer, Solution};
 
-pub fn run() -> io::Result<()> {
-    let input = fs::read_to_string("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+pub struct Day03;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day03 {
+    type Input = String;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
 fn calculate_part1(input: &str) -> u64 {
@@ -46,11 +54,11 @@ fn get_operations(input: &str) -> Vec<Operation> {
 
     re.captures_iter(input)
         .filter_map(|groups| {
-            if let Some(_) = groups.get(4) {
+            if groups.get(4).is_some() {
                 Some(Operation::Do)
-            } else if let Some(_) = groups.get(5) {
+            } else if groups.get(5).is_some() {
                 Some(Operation::Dont)
-            } else if let Some(_) = groups.get(1) {
+            } else if groups.get(1).is_some() {
                 let num1: u64 = groups.get(2)?.as_str().parse().ok()?;
                 let num2: u64 = groups.get(3)?.as_str().parse().ok()?;
                 Some(Operation::Mul(num1, num2))
@@ -72,6 +80,16 @@ enum Operation {
 mod tests {
     use super::*;
 
+    shared::sample_tests! {
+        Day03;
+        file "sample_input_1" {
+            sample_part1: part1 => 161,
+        }
+        file "sample_input_2" {
+            sample_part2: part2 => 48,
+        }
+    }
+
     #[test]
     fn calculate_sample_part1() {
         let res = calculate_part1(
@@ -80,18 +98,9 @@ mod tests {
         assert_eq!(res, 161);
     }
 
-    #[test]
-    fn parse_calc_sample_part1() {
-        let input = fs::read_to_string("sample_input_1");
-        assert!(input.is_ok());
-        let input = input.unwrap();
-        let res = calculate_part1(&input);
-        assert_eq!(res, 161);
-    }
-
     #[test]
     fn test_part2() {
-        let input = fs::read_to_string("sample_input_2");
+        let input = Day03::parse_file(shared::day_file!("sample_input_2"));
         assert!(input.is_ok());
         let input = input.unwrap();
 
@@ -107,8 +116,5 @@ mod tests {
                 Operation::Mul(8, 5)
             ]
         );
-
-        let res = calculate_part2(&input);
-        assert_eq!(res, 48);
     }
 }
@@ -1,51 +1,55 @@
-use shared::parse_2d_map;
-use std::io;
-use strum_macros::EnumIter;
-
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
-
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+use shared::{Answer, Dir8, Grid, Pos2D, Solution};
+use strum::IntoEnumIterator;
+
+type Pos2 = Pos2D<usize>;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        Grid::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
-fn calculate_part1(lines: &[Vec<char>]) -> usize {
-    (0..lines.len())
-        .into_iter()
-        .map(|row| {
-            (0..lines[row].len())
-                .into_iter()
-                .map(|col| count_xmas_from(lines, row, col))
-                .sum::<usize>()
-        })
+fn calculate_part1(lines: &Grid<char>) -> usize {
+    lines
+        .positions()
+        .map(|pos: Pos2| count_xmas_from(lines, pos.row, pos.col))
         .sum()
 }
 
-fn calculate_part2(lines: &[Vec<char>]) -> usize {
-    (1..lines.len() - 1)
-        .into_iter()
-        .map(|row| {
-            (1..lines[row].len() - 1)
-                .into_iter()
-                .map(|col| is_cross_mass(lines, row, col))
-                .filter(|&is_cross| is_cross)
-                .count()
-        })
-        .sum()
+fn calculate_part2(lines: &Grid<char>) -> usize {
+    lines
+        .positions()
+        .filter(|&pos: &Pos2| is_cross_mass(lines, pos.row, pos.col))
+        .count()
 }
 
-fn is_cross_mass(lines: &[Vec<char>], row: usize, col: usize) -> bool {
-    if lines[row][col] != 'A' {
+fn is_cross_mass(lines: &Grid<char>, row: usize, col: usize) -> bool {
+    let pos = Pos2::new(row, col);
+    if lines.get(pos) != Some(&'A') {
         return false;
     }
 
-    let top_left = lines[row - 1][col - 1];
-    let top_right = lines[row - 1][col + 1];
-    let bot_left = lines[row + 1][col - 1];
-    let bot_right = lines[row + 1][col + 1];
+    let corner = |dir: Dir8| {
+        pos.checked_moved(dir)
+            .and_then(|corner| lines.get(corner).copied())
+            .unwrap_or('.')
+    };
+    let top_left = corner(Dir8::UpLeft);
+    let top_right = corner(Dir8::UpRight);
+    let bot_left = corner(Dir8::DownLeft);
+    let bot_right = corner(Dir8::DownRight);
 
     is_m_or_s(top_left)
         && is_m_or_s(top_right)
@@ -65,28 +69,23 @@ fn are_opposite(ch1: char, ch2: char) -> bool {
     }
 }
 
-fn is_xmas_rec(lines: &[Vec<char>], row: usize, col: usize, dir: Direction, letter: char) -> bool {
-    if row >= lines.len() || col >= lines[row].len() {
-        return false;
-    }
-
-    if lines[row][col] != letter {
+fn is_xmas_rec(lines: &Grid<char>, pos: Pos2, dir: Dir8, letter: char) -> bool {
+    if lines.get(pos) != Some(&letter) {
         return false;
     }
 
     match get_next_letter(letter) {
         None => true,
-        Some(next_letter) => match get_next_coord(row, col, dir) {
+        Some(next_letter) => match pos.checked_moved(dir) {
             None => false,
-            Some((next_row, next_col)) => is_xmas_rec(lines, next_row, next_col, dir, next_letter),
+            Some(next_pos) => is_xmas_rec(lines, next_pos, dir, next_letter),
         },
     }
 }
 
-fn count_xmas_from(lines: &[Vec<char>], row: usize, col: usize) -> usize {
-    use strum::IntoEnumIterator;
-    Direction::iter()
-        .map(|dir| is_xmas_rec(&lines, row, col, dir, 'X'))
+fn count_xmas_from(lines: &Grid<char>, row: usize, col: usize) -> usize {
+    Dir8::iter()
+        .map(|dir| is_xmas_rec(lines, Pos2::new(row, col), dir, 'X'))
         .filter(|&is_xmas| is_xmas)
         .count()
 }
@@ -100,51 +99,21 @@ fn get_next_letter(letter: char) -> Option<char> {
     }
 }
 
-fn get_next_coord(row: usize, col: usize, dir: Direction) -> Option<(usize, usize)> {
-    match dir {
-        Direction::East => Some((row, col + 1)),
-        Direction::South => Some((row + 1, col)),
-        Direction::West => Some((row, col.checked_sub(1)?)),
-        Direction::North => Some((row.checked_sub(1)?, col)),
-        Direction::SE => Some((row + 1, col + 1)),
-        Direction::SW => Some((row + 1, col.checked_sub(1)?)),
-        Direction::NW => Some((row.checked_sub(1)?, col.checked_sub(1)?)),
-        Direction::NE => Some((row.checked_sub(1)?, col + 1)),
-    }
-}
-
-fn parse_file(file_path: &str) -> io::Result<Vec<Vec<char>>> {
-    parse_2d_map(file_path)
-}
-
-#[derive(Copy, Clone, EnumIter)]
-enum Direction {
-    East,
-    South,
-    West,
-    North,
-    SE,
-    SW,
-    NW,
-    NE,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_sample() {
-        let input = parse_file("sample_input");
-        assert!(input.is_ok());
-        let input = input.unwrap();
-        let cnt = calculate_part1(&input);
-        assert_eq!(cnt, 18);
+    shared::sample_tests! {
+        Day04;
+        file "sample_input" {
+            sample_part1: part1 => 18,
+            sample_part2: part2 => 9,
+        }
     }
 
     #[test]
     fn test_is_xmas() {
-        let input = parse_file("sample_input");
+        let input = Day04::parse_file(shared::day_file!("sample_input"));
         assert!(input.is_ok());
         let input = input.unwrap();
         assert_eq!(1, count_xmas_from(&input, 0, 4));
@@ -1,16 +1,22 @@
+use shared::{parse, Answer, Solution};
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
-
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Input;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
 fn calculate_part1(input: &Input) -> u64 {
@@ -56,25 +62,16 @@ fn is_corrrectly_ordered(update: &[u64], order: &Order) -> bool {
     true
 }
 
-fn parse_file(file_path: &str) -> io::Result<Input> {
-    let file = File::open(file_path)?;
-    let reader = io::BufReader::new(file);
-
+fn parse_input(input: &str) -> shared::Result<Input> {
     let mut order = HashMap::new();
     let mut updates = Vec::new();
 
-    for line in reader.lines() {
-        let line = line?;
-        if line.contains('|') {
-            let parts: Vec<&str> = line.split('|').collect();
-            if let (Some(a), Some(b)) = (parts.get(0), parts.get(1)) {
-                let a: u64 = a.trim().parse().unwrap();
-                let b: u64 = b.trim().parse().unwrap();
-                order.entry(a).or_insert_with(HashSet::new).insert(b);
-            }
-        } else if line.contains(',') {
-            let numbers: Vec<u64> = line.split(',').map(|s| s.trim().parse().unwrap()).collect();
-            updates.push(numbers);
+    for line in parse::non_empty_lines(input) {
+        if line.text.contains('|') {
+            let (a, b) = line.pair(line.text, "|")?;
+            order.entry(a).or_insert_with(HashSet::new).insert(b);
+        } else {
+            updates.push(line.list(line.text, ",")?);
         }
     }
 
@@ -82,7 +79,7 @@ fn parse_file(file_path: &str) -> io::Result<Input> {
 }
 
 type Order = HashMap<u64, HashSet<u64>>;
-struct Input {
+pub struct Input {
     order: HashMap<u64, HashSet<u64>>,
     updates: Vec<Vec<u64>>,
 }
@@ -91,9 +88,17 @@ struct Input {
 mod tests {
     use super::*;
 
+    shared::sample_tests! {
+        Day05;
+        file "sample_input" {
+            sample_part1: part1 => 143,
+            sample_part2: part2 => 123,
+        }
+    }
+
     #[test]
     fn sample_test() {
-        let input = parse_file("sample_input");
+        let input = Day05::parse_file(shared::day_file!("sample_input"));
         assert!(input.is_ok());
         let input = input.unwrap();
 
@@ -103,11 +108,5 @@ mod tests {
             .map(|update| is_corrrectly_ordered(update, &input.order))
             .collect();
         assert_eq!(res, vec![true, true, true, false, false, false]);
-
-        let calc = calculate_part1(&input);
-        assert_eq!(calc, 143);
-
-        let calc2 = calculate_part2(&input);
-        assert_eq!(calc2, 123);
     }
 }
@@ -1,59 +1,57 @@
-use shared::parse_2d_map;
+use shared::{Answer, Dir, Error, Grid, Pos2D, Solution};
 use std::collections::HashSet;
-use std::io;
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+type Pos2 = Pos2D<usize>;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        let map = Grid::parse(input)?;
+        match find_starting_pos(&map) {
+            Some(_) => Ok(map),
+            None => Err(Error::InvalidInput("No guard `^` found".to_string())),
+        }
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
-fn calculate_part1(input: &Vec<Vec<char>>) -> usize {
+fn calculate_part1(input: &Grid<char>) -> usize {
     let mut guard = Guard::from_input(input);
     guard.patrol();
     guard.count_xs()
 }
 
-fn calculate_part2(input: &Vec<Vec<char>>) -> usize {
+fn calculate_part2(input: &Grid<char>) -> usize {
     let guard = Guard::from_input(input);
     guard.count_possible_loops()
 }
 
-fn find_starting_pos(matrix: &Vec<Vec<char>>) -> Option<(usize, usize)> {
-    for (row, line) in matrix.iter().enumerate() {
-        for (col, &ch) in line.iter().enumerate() {
-            if ch == '^' {
-                return Some((row, col));
-            }
-        }
-    }
-
-    None
-}
-
-fn parse_file(file_path: &str) -> io::Result<Vec<Vec<char>>> {
-    parse_2d_map(file_path)
+fn find_starting_pos(matrix: &Grid<char>) -> Option<Pos2> {
+    matrix.find(&'^')
 }
 
 #[derive(Clone)]
 struct Guard {
-    matrix: Vec<Vec<char>>,
-    curr_row: usize,
-    curr_col: usize,
-    pos_and_dirs: HashSet<(usize, usize, char)>,
+    matrix: Grid<char>,
+    curr_pos: Pos2,
+    pos_and_dirs: HashSet<(Pos2, char)>,
 }
 
 impl Guard {
-    fn from_input(input: &Vec<Vec<char>>) -> Self {
-        let (row, col) = find_starting_pos(input).unwrap();
+    fn from_input(input: &Grid<char>) -> Self {
         Guard {
             matrix: input.clone(),
-            curr_row: row,
-            curr_col: col,
+            curr_pos: find_starting_pos(input).unwrap(),
             pos_and_dirs: HashSet::new(),
         }
     }
@@ -75,12 +73,12 @@ impl Guard {
         let mut taken_steps = HashSet::new();
         let mut current_state = self.clone();
 
-        while let Some((next_row, next_col)) = current_state.get_next_move() {
+        while let Some(next_pos) = current_state.get_next_move() {
             let mut new_state = self.clone();
-            new_state.matrix[next_row][next_col] = '#';
+            new_state.matrix[next_pos] = '#';
 
             if !new_state.patrol() {
-                taken_steps.insert((next_row, next_col));
+                taken_steps.insert(next_pos);
             }
 
             current_state.move_once();
@@ -94,7 +92,7 @@ impl Guard {
     /// - `Some(true)` if the guard moved into a new position on the board
     /// - `Some(false)` if the guard moved to a position of an infinite loop
     fn move_once(&mut self) -> Option<bool> {
-        let curr_pos_dir = (self.curr_row, self.curr_col, self.get_dir());
+        let curr_pos_dir = (self.curr_pos, self.get_dir());
         let been_there = !self.pos_and_dirs.insert(curr_pos_dir);
         if been_there {
             return Some(false);
@@ -109,11 +107,11 @@ impl Guard {
     }
 
     fn get_dir(&self) -> char {
-        self.matrix[self.curr_row][self.curr_col]
+        self.matrix[self.curr_pos]
     }
 
     fn set_dir(&mut self, dir: char) {
-        self.matrix[self.curr_row][self.curr_col] = dir;
+        self.matrix[self.curr_pos] = dir;
     }
 
     fn rotate_90(&mut self) {
@@ -127,46 +125,32 @@ impl Guard {
         self.set_dir(ch);
     }
 
-    fn move_to(&mut self, (row, col): (usize, usize)) {
-        self.matrix[row][col] = self.get_dir();
+    fn move_to(&mut self, pos: Pos2) {
+        self.matrix[pos] = self.get_dir();
         self.set_dir('X');
-        self.curr_row = row;
-        self.curr_col = col;
+        self.curr_pos = pos;
     }
 
-    fn get_next_move(&mut self) -> Option<(usize, usize)> {
-        if let Some((next_row, next_col)) = self.get_pos_infront() {
-            if self.matrix[next_row][next_col] == '#' {
+    fn get_next_move(&mut self) -> Option<Pos2> {
+        if let Some(next_pos) = self.get_pos_infront() {
+            if self.matrix[next_pos] == '#' {
                 self.rotate_90();
                 return self.get_next_move(); // TODO: Potential infinite loop
             }
-            Some((next_row, next_col))
+            Some(next_pos)
         } else {
-            self.matrix[self.curr_row][self.curr_col] = 'X';
+            self.set_dir('X');
             None
         }
     }
 
-    fn bound_move(&self, row: usize, col: usize) -> Option<(usize, usize)> {
-        (row < self.matrix.len() && col < self.matrix[row].len()).then_some((row, col))
-    }
-
-    fn get_pos_infront(&self) -> Option<(usize, usize)> {
-        let coord = match self.matrix[self.curr_row][self.curr_col] {
-            '^' => (self.curr_row.checked_sub(1)?, self.curr_col),
-            '>' => self.bound_move(self.curr_row, self.curr_col + 1)?,
-            'v' => self.bound_move(self.curr_row + 1, self.curr_col)?,
-            '<' => (self.curr_row, self.curr_col.checked_sub(1)?),
-            _ => return None,
-        };
-        Some(coord)
+    fn get_pos_infront(&self) -> Option<Pos2> {
+        let dir = Dir::from_char(self.get_dir())?;
+        self.matrix.moved(self.curr_pos, dir)
     }
 
     fn count_xs(&self) -> usize {
-        self.matrix
-            .iter()
-            .map(|line| line.iter().filter(|&&ch| ch == 'X').count())
-            .sum()
+        self.matrix.values().filter(|&&ch| ch == 'X').count()
     }
 }
 
@@ -174,14 +158,22 @@ impl Guard {
 mod tests {
     use super::*;
 
+    shared::sample_tests! {
+        Day06;
+        file "sample_input" {
+            sample_part1: part1 => 41,
+            sample_part2: part2 => 6,
+        }
+    }
+
     #[test]
     fn sample_test() {
-        let input = parse_file("sample_input");
+        let input = Day06::parse_file(shared::day_file!("sample_input"));
         assert!(input.is_ok());
         let input = input.unwrap();
         assert_eq!(
             input,
-            vec![
+            Grid::from_rows(vec![
                 vec!['.', '.', '.', '.', '#', '.', '.', '.', '.', '.'],
                 vec!['.', '.', '.', '.', '.', '.', '.', '.', '.', '#'],
                 vec!['.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
@@ -192,41 +184,37 @@ mod tests {
                 vec!['.', '.', '.', '.', '.', '.', '.', '.', '#', '.'],
                 vec!['#', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
                 vec!['.', '.', '.', '.', '.', '.', '#', '.', '.', '.'],
-            ]
+            ])
+            .unwrap()
         );
-
-        let res_xs = calculate_part1(&input);
-        assert_eq!(res_xs, 41);
-
-        let guard = Guard::from_input(&input);
-        let res_loops = guard.count_possible_loops();
-        assert_eq!(res_loops, 6);
     }
 
     #[test]
     fn test_loop() {
-        let input = vec![
+        let input = Grid::from_rows(vec![
             vec!['.', '#', '.', '.'],
             vec!['.', '.', '.', '#'],
             vec!['.', '.', '.', '.'],
             vec!['#', '.', '.', '.'],
             vec!['.', '^', '#', '.'],
-        ];
+        ])
+        .unwrap();
 
         let mut guard = Guard::from_input(&input);
         let no_loop = guard.patrol();
-        assert_eq!(no_loop, false);
+        assert!(!no_loop);
 
-        let input = vec![
+        let input = Grid::from_rows(vec![
             vec!['.', '#', '.', '.'],
             vec!['.', '.', '.', '#'],
             vec!['.', '.', '.', '.'],
             vec!['#', '.', '.', '.'],
             vec!['.', '^', '.', '.'],
-        ];
+        ])
+        .unwrap();
 
         let mut guard = Guard::from_input(&input);
         let no_loop = guard.patrol();
-        assert_eq!(no_loop, true);
+        assert!(no_loop);
     }
 }
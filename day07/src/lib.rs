@@ -1,15 +1,21 @@
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
-
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+use shared::{parse_field, Answer, Error, Solution};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<Equation>;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
 fn calculate_part1(input: &[Equation]) -> u64 {
@@ -29,7 +35,7 @@ fn calculate_part2(input: &[Equation]) -> u64 {
 }
 
 #[derive(Debug, PartialEq)]
-struct Equation {
+pub struct Equation {
     res: u64,
     operands: Vec<u64>,
 }
@@ -90,32 +96,68 @@ impl Equation {
     }
 }
 
-fn parse_file(file_path: &str) -> io::Result<Vec<Equation>> {
-    Ok(File::open(file_path)
-        .map(io::BufReader::new)?
+fn parse_input(input: &str) -> shared::Result<Vec<Equation>> {
+    input
         .lines()
-        .filter_map(Result::ok)
-        .map(|line| {
-            let parts: Vec<_> = line.split(':').collect();
-            assert_eq!(parts.len(), 2);
-            let res = parts[0].parse::<u64>().unwrap();
-            let operands: Vec<u64> = parts[1]
-                .trim()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            let (res, operands_part) = line
+                .split_once(':')
+                .ok_or_else(|| Error::parse(idx + 1, 1, "expected `<result>: <operands>`"))?;
+            let res = parse_field(idx + 1, line, res)?;
+            let operands: Vec<_> = operands_part
                 .split_whitespace()
-                .map(|s| s.parse().unwrap())
-                .collect();
-            Equation { res, operands }
+                .map(|s| parse_field(idx + 1, line, s))
+                .collect::<shared::Result<_>>()?;
+            if operands.is_empty() {
+                return Err(Error::parse_at(
+                    idx + 1,
+                    line,
+                    operands_part,
+                    "expected operands",
+                ));
+            }
+            Ok(Equation { res, operands })
         })
-        .collect())
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    shared::sample_tests! {
+        Day07;
+        file "sample_input" {
+            sample_part1: part1 => 3749,
+            sample_part2: part2 => 11387,
+        }
+    }
+
+    #[test]
+    fn test_malformed_equations() {
+        assert!(matches!(
+            Day07::parse("190: 10 19\n3267 81 40 27\n"),
+            Err(Error::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            Day07::parse("190: 10 1x9\n"),
+            Err(Error::Parse {
+                line: 1,
+                column: 9,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Day07::parse("190: 10 19\n5:\n"),
+            Err(Error::Parse { line: 2, .. })
+        ));
+    }
+
     #[test]
     fn test_sample() {
-        let input = parse_file("sample_input");
+        let input = Day07::parse_file(shared::day_file!("sample_input"));
         assert!(input.is_ok());
         let input = input.unwrap();
 
@@ -160,11 +202,5 @@ mod tests {
                 },
             ]
         );
-
-        let res1 = calculate_part1(&input);
-        assert_eq!(res1, 3749);
-
-        let res2 = calculate_part2(&input);
-        assert_eq!(res2, 11387);
     }
 }
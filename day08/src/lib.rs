@@ -1,11 +1,10 @@
-use shared::{parse_2d_map, Pos2D, Vec2D};
+use shared::log::Verbosity;
+use shared::{Answer, Grid, Pos2D, Solution, Vec2D};
 use std::collections::HashSet;
-use std::io;
 
 type CoordType = i64;
 type Pos2 = Pos2D<CoordType>;
 type Vec2 = Vec2D<CoordType>;
-type Limit = Pos2;
 
 #[derive(Copy, Clone)]
 enum Extended {
@@ -13,33 +12,39 @@ enum Extended {
     False,
 }
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+pub struct Day08;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day08 {
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        Grid::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
-fn calculate_part1(input: &[Vec<char>]) -> usize {
-    let limit = Limit::new(input.len() as CoordType, input[0].len() as CoordType);
-    let antennas = get_antennas(&input);
-    let antinodes = get_antinodes(&antennas, limit, Extended::False);
-    print_antinodes(input, &antinodes);
+fn calculate_part1(input: &Grid<char>) -> usize {
+    let antennas = get_antennas(input);
+    let antinodes = get_antinodes(&antennas, input, Extended::False);
+    trace_antinodes(input, &antinodes);
     antinodes.len()
 }
 
-fn calculate_part2(input: &[Vec<char>]) -> usize {
-    let limit = Limit::new(input.len() as CoordType, input[0].len() as CoordType);
-    let antennas = get_antennas(&input);
-    let antinodes = get_antinodes(&antennas, limit, Extended::True);
-    print_antinodes(input, &antinodes);
+fn calculate_part2(input: &Grid<char>) -> usize {
+    let antennas = get_antennas(input);
+    let antinodes = get_antinodes(&antennas, input, Extended::True);
+    trace_antinodes(input, &antinodes);
     antinodes.len()
 }
 
-fn get_antinodes(antennas: &[Antenna], limit: Limit, extended: Extended) -> HashSet<Antinode> {
+fn get_antinodes(antennas: &[Antenna], map: &Grid<char>, extended: Extended) -> HashSet<Antinode> {
     let mut set = HashSet::new();
 
     for antenna1 in antennas {
@@ -48,7 +53,7 @@ fn get_antinodes(antennas: &[Antenna], limit: Limit, extended: Extended) -> Hash
                 continue;
             }
 
-            let antinodes = antenna1.get_antinodes(antenna2, limit, extended);
+            let antinodes = antenna1.get_antinodes(antenna2, map, extended);
             for antinode in antinodes {
                 set.insert(antinode);
             }
@@ -62,20 +67,12 @@ fn get_antinodes(antennas: &[Antenna], limit: Limit, extended: Extended) -> Hash
     set
 }
 
-fn get_antennas(input: &[Vec<char>]) -> Vec<Antenna> {
-    let mut res = vec![];
-    for (row, line) in input.iter().enumerate() {
-        for (col, &ch) in line.iter().enumerate() {
-            if ch != '.' {
-                res.push(Antenna::new(row, col, ch));
-            }
-        }
-    }
-    res
-}
-
-fn parse_file(file_path: &str) -> io::Result<Vec<Vec<char>>> {
-    parse_2d_map(file_path)
+fn get_antennas(input: &Grid<char>) -> Vec<Antenna> {
+    input
+        .iter()
+        .filter(|(_, &ch)| ch != '.')
+        .map(|(pos, &ch)| Antenna::new(pos, ch))
+        .collect()
 }
 
 #[derive(PartialEq)]
@@ -85,14 +82,14 @@ struct Antenna {
 }
 
 impl Antenna {
-    fn new(row: usize, col: usize, ch: char) -> Self {
+    fn new(pos: Pos2D<usize>, ch: char) -> Self {
         Self {
-            pos: Pos2::new(row as CoordType, col as CoordType),
+            pos: pos.try_cast().expect("map coordinates fit in i64"),
             ch,
         }
     }
 
-    fn get_antinodes(&self, other: &Self, limit: Limit, extended: Extended) -> Vec<Antinode> {
+    fn get_antinodes(&self, other: &Self, map: &Grid<char>, extended: Extended) -> Vec<Antinode> {
         if self.ch != other.ch {
             return vec![];
         }
@@ -100,8 +97,8 @@ impl Antenna {
         let vec1 = self.pos.make_vec_to(other.pos);
         let vec2 = other.pos.make_vec_to(self.pos);
 
-        let antinode1 = Antinode::from_pos_limited((vec1 + other.pos).to_pos(), limit);
-        let antinode2 = Antinode::from_pos_limited((vec2 + self.pos).to_pos(), limit);
+        let antinode1 = Antinode::from_pos_limited(other.pos + vec1, map);
+        let antinode2 = Antinode::from_pos_limited(self.pos + vec2, map);
 
         let mut res = vec![];
 
@@ -117,7 +114,7 @@ impl Antenna {
             let mut add_antinode_sequence = |mut node_opt: Option<Antinode>, vec: Vec2| {
                 while let Some(node) = node_opt {
                     res.push(node);
-                    node_opt = Antinode::from_pos_limited((vec + node.pos).to_pos(), limit);
+                    node_opt = Antinode::from_pos_limited(node.pos + vec, map);
                 }
             };
 
@@ -135,34 +132,39 @@ struct Antinode {
 }
 
 impl Antinode {
-    fn new(row: usize, col: usize) -> Self {
-        Self {
-            pos: Pos2::new(row as CoordType, col as CoordType),
-        }
-    }
-
     fn from_pos(pos: Pos2) -> Self {
         Self { pos }
     }
 
-    fn from_pos_limited(pos: Pos2, limit: Limit) -> Option<Self> {
-        if pos.row >= 0 && pos.col >= 0 && pos.row < limit.row && pos.col < limit.col {
-            Some(Antinode::from_pos(pos))
-        } else {
-            None
+    fn from_pos_limited(pos: Pos2, map: &Grid<char>) -> Option<Self> {
+        let inside = pos.try_cast::<usize>().is_some_and(|pos| map.contains(pos));
+        inside.then_some(Antinode::from_pos(pos))
+    }
+}
+
+fn trace_antinodes(input: &Grid<char>, antinodes: &HashSet<Antinode>) {
+    if !shared::log::enabled(Verbosity::Trace) {
+        return;
+    }
+
+    let mut map = input.clone();
+    for antinode in antinodes {
+        if let Some(pos) = antinode.pos.try_cast::<usize>() {
+            map[pos] = '#';
         }
     }
+    shared::trace!("{map}");
 }
 
-fn print_antinodes(input: &[Vec<char>], antinodes: &HashSet<Antinode>) {
-    for (row, line) in input.iter().enumerate() {
-        for (col, &ch) in line.iter().enumerate() {
-            if antinodes.contains(&Antinode::new(row, col)) {
-                print!("#");
-            } else {
-                print!("{ch}");
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    shared::sample_tests! {
+        Day08;
+        file "sample_input" {
+            sample_part1: part1 => 14,
+            sample_part2: part2 => 34,
         }
-        println!();
     }
 }
@@ -1,19 +1,38 @@
-use std::fs;
-use std::io;
+use shared::{Answer, Error, Solution};
 
 enum Part {
     One,
     Two,
 }
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+pub struct Day09;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day09 {
+    type Input = Vec<char>;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        let line = input.trim_end();
+        if line.is_empty() {
+            return Err(Error::InvalidInput("The disk map is empty".to_string()));
+        }
+        match line.char_indices().find(|(_, ch)| !ch.is_ascii_digit()) {
+            Some((idx, ch)) => Err(Error::parse_at(
+                1,
+                line,
+                &line[idx..],
+                format!("expected a digit, found `{ch}`"),
+            )),
+            None => Ok(line.chars().collect()),
+        }
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
 fn calculate_part1(input: &[char]) -> usize {
@@ -50,7 +69,7 @@ fn calculate_part(input: &[char], part: Part) -> usize {
     calculate_checksum(&vec)
 }
 
-fn rearrange_part1(vec: &mut Vec<FileId>) {
+fn rearrange_part1(vec: &mut [FileId]) {
     while let Some(free_space_idx) = vec.iter().position(|&id| id == FileId::EmptySpace) {
         if let Some(block_idx) = vec.iter().rposition(|&id| id != FileId::EmptySpace) {
             if free_space_idx > block_idx {
@@ -62,21 +81,21 @@ fn rearrange_part1(vec: &mut Vec<FileId>) {
     }
 }
 
-fn rearrange_part2(vec: &mut Vec<FileId>) {
+fn rearrange_part2(vec: &mut [FileId]) {
     let mut next_file_block = find_next_file(vec, 0);
-    loop {
-        if let Some(empty_pos) = find_empty_space(vec, next_file_block.len()) {
-            let id = vec[next_file_block.start_pos];
-            if empty_pos < next_file_block.start_pos {
-                vec[next_file_block.start_pos..next_file_block.end_pos].fill(FileId::EmptySpace);
-                vec[empty_pos..empty_pos + next_file_block.len()].fill(id);
+    while let Some(file_block) = next_file_block {
+        if let Some(empty_pos) = find_empty_space(vec, file_block.len()) {
+            let id = vec[file_block.start_pos];
+            if empty_pos < file_block.start_pos {
+                vec[file_block.start_pos..file_block.end_pos].fill(FileId::EmptySpace);
+                vec[empty_pos..empty_pos + file_block.len()].fill(id);
             }
         }
 
-        if next_file_block.start_pos == 0 {
+        if file_block.start_pos == 0 {
             break;
         }
-        next_file_block = find_next_file(vec, vec.len() - next_file_block.start_pos);
+        next_file_block = find_next_file(vec, vec.len() - file_block.start_pos);
     }
 }
 
@@ -87,14 +106,13 @@ fn calculate_checksum(vec: &[FileId]) -> usize {
     })
 }
 
-fn find_next_file(vec: &[FileId], skip: usize) -> FileBlock {
+fn find_next_file(vec: &[FileId], skip: usize) -> Option<FileBlock> {
     let start_pos = vec
         .iter()
         .rev()
         .skip(skip)
-        .position(|&id| id != FileId::EmptySpace)
-        .unwrap();
-    let id = vec.iter().rev().skip(skip).skip(start_pos).next().unwrap();
+        .position(|&id| id != FileId::EmptySpace)?;
+    let id = vec.iter().rev().skip(skip).nth(start_pos).unwrap();
     let end_pos = vec
         .iter()
         .rev()
@@ -104,11 +122,11 @@ fn find_next_file(vec: &[FileId], skip: usize) -> FileBlock {
         .find(|(_, &curr_id)| curr_id != *id)
         .map_or(vec.len() - skip, |(pos, _)| pos);
 
-    FileBlock {
+    let block = FileBlock {
         start_pos: skip + start_pos,
         end_pos: skip + end_pos,
-    }
-    .unrev(vec.len())
+    };
+    Some(block.unrev(vec.len()))
 }
 
 fn find_empty_space(vec: &[FileId], size: usize) -> Option<usize> {
@@ -126,10 +144,6 @@ fn find_empty_space(vec: &[FileId], size: usize) -> Option<usize> {
         .map(|(i, _)| i)
 }
 
-fn parse_file(file_path: &str) -> io::Result<Vec<char>> {
-    fs::read_to_string(file_path).map(|contents| contents.trim_end().chars().collect())
-}
-
 #[derive(Copy, Clone)]
 struct FileBlock {
     start_pos: usize,
@@ -160,3 +174,29 @@ enum FileId {
     EmptySpace,
     Id(usize),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    shared::sample_tests! {
+        Day09;
+        file "sample_input" {
+            sample_part1: part1 => 1928,
+            sample_part2: part2 => 2858,
+        }
+        text "0110" {
+            leading_empty_file_part1: part1 => 0,
+            leading_empty_file_part2: part2 => 0,
+        }
+    }
+
+    #[test]
+    fn invalid_disk_maps_are_rejected() {
+        assert!(Day09::parse("\n").is_err());
+        assert!(matches!(
+            Day09::parse("12x4"),
+            Err(Error::Parse { column: 3, .. })
+        ));
+    }
+}
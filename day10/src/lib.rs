@@ -1,35 +1,42 @@
-use shared::{parse_2d_map, Pos2D};
+use shared::{parse_2d_map, Answer, Error, Grid, Pos2D, Solution};
 use std::collections::HashSet;
-use std::io;
 
 type DataType = usize;
 type Pos2 = Pos2D<usize>;
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+pub struct Day10;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day10 {
+    type Input = Grid<DataType>;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
-fn calculate_part1(input: &[Vec<DataType>]) -> usize {
+fn calculate_part1(input: &Grid<DataType>) -> usize {
     find_zeros(input)
         .into_iter()
         .map(|pos| count_reachable_nines(input, pos))
         .sum()
 }
 
-fn calculate_part2(input: &[Vec<DataType>]) -> usize {
+fn calculate_part2(input: &Grid<DataType>) -> usize {
     find_zeros(input)
         .into_iter()
         .map(|pos| rate_hike(input, pos))
         .sum()
 }
 
-fn count_reachable_nines(matrix: &[Vec<DataType>], start_pos: Pos2) -> usize {
+fn count_reachable_nines(matrix: &Grid<DataType>, start_pos: Pos2) -> usize {
     let mut visited = HashSet::new();
     let mut res_cnt = 0_usize;
     count_reachable_nines_rec(matrix, start_pos, &mut res_cnt, &mut visited);
@@ -37,7 +44,7 @@ fn count_reachable_nines(matrix: &[Vec<DataType>], start_pos: Pos2) -> usize {
 }
 
 fn count_reachable_nines_rec(
-    matrix: &[Vec<DataType>],
+    matrix: &Grid<DataType>,
     pos: Pos2,
     cnt: &mut usize,
     visited: &mut HashSet<Pos2>,
@@ -46,7 +53,7 @@ fn count_reachable_nines_rec(
         return;
     }
 
-    let curr_val = matrix[pos.row][pos.col];
+    let curr_val = matrix[pos];
     if curr_val == 9 {
         *cnt += 1;
         return;
@@ -57,18 +64,18 @@ fn count_reachable_nines_rec(
     }
 }
 
-fn rate_hike(matrix: &[Vec<DataType>], start_pos: Pos2) -> usize {
+fn rate_hike(matrix: &Grid<DataType>, start_pos: Pos2) -> usize {
     let mut res_cnt = 0_usize;
     rate_hike_rec(matrix, start_pos, &mut res_cnt, HashSet::new());
     res_cnt
 }
 
-fn rate_hike_rec(matrix: &[Vec<DataType>], pos: Pos2, cnt: &mut usize, mut visited: HashSet<Pos2>) {
+fn rate_hike_rec(matrix: &Grid<DataType>, pos: Pos2, cnt: &mut usize, mut visited: HashSet<Pos2>) {
     if !visited.insert(pos) {
         return;
     }
 
-    let curr_val = matrix[pos.row][pos.col];
+    let curr_val = matrix[pos];
     if curr_val == 9 {
         *cnt += 1;
         return;
@@ -85,64 +92,48 @@ fn rate_hike_rec(matrix: &[Vec<DataType>], pos: Pos2, cnt: &mut usize, mut visit
     }
 }
 
-fn find_zeros(matrix: &[Vec<DataType>]) -> Vec<Pos2> {
+fn find_zeros(matrix: &Grid<DataType>) -> Vec<Pos2> {
+    matrix.find_all(&0).collect()
+}
+
+fn get_neighbours(matrix: &Grid<DataType>, pos: Pos2) -> Vec<Pos2> {
+    let curr_val = matrix[pos];
     matrix
+        .neighbours(pos)
+        .filter(|&cpos| matrix[cpos] == curr_val + 1)
+        .collect()
+}
+
+fn parse_input(input: &str) -> shared::Result<Grid<DataType>> {
+    let rows = parse_2d_map(input)
         .iter()
         .enumerate()
-        .flat_map(|(row, line)| {
+        .map(|(row, line)| {
             line.iter()
                 .enumerate()
-                .filter(|(_, &value)| value == 0)
-                .map(move |(col, _)| Pos2 { row, col })
+                .map(|(col, &ch)| match ch.to_digit(10) {
+                    Some(digit) => Ok(digit as DataType),
+                    None => Err(Error::parse(
+                        row + 1,
+                        col + 1,
+                        format!("expected a height digit, found `{ch}`"),
+                    )),
+                })
+                .collect()
         })
-        .collect()
+        .collect::<shared::Result<_>>()?;
+    Grid::from_rows(rows)
 }
 
-fn get_neighbours(matrix: &[Vec<DataType>], pos: Pos2) -> Vec<Pos2> {
-    let mut res = vec![];
-    let curr_val = matrix[pos.row][pos.col];
-
-    if pos.row > 0 {
-        res.push(Pos2 {
-            row: pos.row - 1,
-            col: pos.col,
-        });
-    }
-
-    if pos.col > 0 {
-        res.push(Pos2 {
-            row: pos.row,
-            col: pos.col - 1,
-        });
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if pos.row < matrix.len() - 1 {
-        res.push(Pos2 {
-            row: pos.row + 1,
-            col: pos.col,
-        });
-    }
-
-    if pos.col < matrix[pos.row].len() - 1 {
-        res.push(Pos2 {
-            row: pos.row,
-            col: pos.col + 1,
-        });
+    shared::sample_tests! {
+        Day10;
+        file "sample_input" {
+            sample_part1: part1 => 36,
+            sample_part2: part2 => 81,
+        }
     }
-
-    res.into_iter()
-        .filter(|cpos| matrix[cpos.row][cpos.col] == curr_val + 1)
-        .collect()
-}
-
-fn parse_file(file_path: &str) -> std::io::Result<Vec<Vec<DataType>>> {
-    Ok(parse_2d_map(file_path)?
-        .iter()
-        .map(|line| {
-            line.iter()
-                .filter_map(|&c| c.to_digit(10))
-                .map(|d| d as DataType)
-                .collect()
-        })
-        .collect())
 }
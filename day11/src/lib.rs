@@ -1,32 +1,54 @@
+use shared::{parse_field, Answer, Params, Solution};
 use std::collections::HashMap;
-use std::fs;
-use std::io;
 
 type DataType = u64;
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+pub struct Day11;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day11 {
+    type Input = Stones;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> shared::Result<Self::Input> {
+        Ok(Stones {
+            stones: parse_input(input)?,
+            part1_blinks: params.get_or("part1_blinks", 25)?,
+            part2_blinks: params.get_or("part2_blinks", 75)?,
+        })
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
-fn calculate_part1(input: &[DataType]) -> usize {
-    let mut stones = input.to_vec();
-    (0..25).for_each(|_| stones = do_blink(&stones));
+/// The engraved stones and how many times to blink at them in each part.
+pub struct Stones {
+    stones: Vec<DataType>,
+    part1_blinks: usize,
+    part2_blinks: usize,
+}
+
+fn calculate_part1(input: &Stones) -> usize {
+    let mut stones = input.stones.to_vec();
+    (0..input.part1_blinks).for_each(|_| stones = do_blink(&stones));
     stones.len()
 }
 
-fn calculate_part2(input: &[DataType]) -> usize {
+fn calculate_part2(input: &Stones) -> usize {
     let mut map = HashMap::new();
-    input.iter().for_each(|&stone| {
+    input.stones.iter().for_each(|&stone| {
         *map.entry(stone).or_default() += 1;
     });
 
-    (0..75).for_each(|_| map = do_blink_map(&map));
+    (0..input.part2_blinks).for_each(|_| map = do_blink_map(&map));
 
     map.values().sum()
 }
@@ -70,7 +92,7 @@ fn process_stone(stone: DataType) -> BlinkRes {
     }
 
     let digits_cnt = digits_cnt(stone);
-    if digits_cnt % 2 == 0 {
+    if digits_cnt.is_multiple_of(2) {
         let split = split_number(stone, digits_cnt / 2);
         BlinkRes::Split(split)
     } else {
@@ -93,9 +115,26 @@ fn split_number(n: DataType, index: usize) -> (DataType, DataType) {
     (n / divisor, n % divisor)
 }
 
-fn parse_file(file_path: &str) -> io::Result<Vec<DataType>> {
-    Ok(fs::read_to_string(file_path)?
-        .split_whitespace()
-        .filter_map(|s| s.parse::<DataType>().ok())
-        .collect())
+fn parse_input(input: &str) -> shared::Result<Vec<DataType>> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(idx, line)| {
+            line.split_whitespace()
+                .map(move |s| parse_field(idx + 1, line, s))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    shared::sample_tests! {
+        Day11;
+        file "sample_input" {
+            sample_part1: part1 => 55312,
+            sample_part2: part2 => 65601038650482_u64,
+        }
+    }
 }
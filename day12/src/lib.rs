@@ -1,24 +1,31 @@
-use shared::{dir_in, hashset_dirs_to_vec, parse_2d_map, Dir, Pos2D};
+use shared::{dir_in, hashset_dirs_to_vec, Answer, Dir, Grid, Pos2D, Solution};
 use std::collections::HashSet;
-use std::io;
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+pub struct Day12;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day12 {
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        Grid::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
-fn calculate_part1(input: &[Vec<char>]) -> usize {
+fn calculate_part1(input: &Grid<char>) -> usize {
     let mut garden = Garden::new(input);
     garden.fence_up();
     garden.calc_price()
 }
 
-fn calculate_part2(input: &[Vec<char>]) -> usize {
+fn calculate_part2(input: &Grid<char>) -> usize {
     let mut garden = Garden::new(input);
     garden.fence_up();
     garden.calc_discount_price()
@@ -39,7 +46,7 @@ struct Region {
 }
 
 struct Garden {
-    matrix: Vec<Vec<Plot>>,
+    matrix: Grid<Plot>,
     regions: Vec<Region>,
 }
 
@@ -50,20 +57,16 @@ struct Neighbour {
 }
 
 impl Garden {
-    fn new(input: &[Vec<char>]) -> Garden {
-        let matrix = input
-            .iter()
-            .map(|line| line.iter().map(|&ch| Plot::from_char(ch)).collect())
-            .collect();
+    fn new(input: &Grid<char>) -> Garden {
         Garden {
-            matrix,
+            matrix: input.map(|&ch| Plot::from_char(ch)),
             regions: vec![],
         }
     }
 
     fn fence_up(&mut self) {
-        for row in 0..self.matrix.len() {
-            for col in 0..self.matrix[row].len() {
+        for row in 0..self.matrix.height() {
+            for col in 0..self.matrix.width() {
                 self.fence_region_at_pos(Pos2::new(row, col));
             }
         }
@@ -90,7 +93,7 @@ impl Garden {
     fn calc_region_discount_price(&self, region: Region) -> usize {
         let area = self.calc_area(region);
         let sides = self.calc_sides(region.pos);
-        println!(
+        shared::info!(
             "A region of {} plants with price {} * {} = {}",
             region.ch,
             area,
@@ -136,9 +139,10 @@ impl Garden {
         }
 
         if let Some(dir) = from {
-            let from_pos = pos.moved(dir);
-            self.get_plot_mut(from_pos).fences.remove(&dir.opposite());
-            self.get_plot_mut(pos).fences.remove(&dir);
+            if let Some(from_pos) = pos.moved_within(dir, self.matrix.size()) {
+                self.get_plot_mut(from_pos).fences.remove(&dir.opposite());
+                self.get_plot_mut(pos).fences.remove(&dir);
+            }
         }
 
         if self.plot_has_region(pos) {
@@ -180,10 +184,6 @@ fn set_not_contains(set: Option<&HashSet<Dir>>, dir: Dir) -> bool {
     }
 }
 
-fn parse_file(file_path: &str) -> std::io::Result<Vec<Vec<char>>> {
-    parse_2d_map(file_path)
-}
-
 /**************************************************************/
 /******** Despicable code ahead. Proceed with caution! ********/
 /**************************************************************/
@@ -208,36 +208,36 @@ impl Garden {
                 // U_L_  |----
                 //       |^     case 3
                 //       |
-                println!("case 03: Adding top left of {:?}", pos);
+                shared::trace!("case 03: Adding top left of {:?}", pos);
                 *corners += 2;
                 let right_plot_fences = self.right_plot_fences(pos);
                 if set_not_contains(right_plot_fences, Dir::Up) {
-                    println!("case 03: Adding top right of {:?}", pos);
+                    shared::trace!("case 03: Adding top right of {:?}", pos);
                     *corners += 1;
                 }
                 let bot_plot_fences = self.bot_plot_fences(pos);
                 if set_not_contains(bot_plot_fences, Dir::Left) {
-                    println!("case 03: Adding bot left of {:?}", pos);
+                    shared::trace!("case 03: Adding bot left of {:?}", pos);
                     *corners += 1;
                 }
                 if dir_in(&fences_arr, Dir::Down) {
                     // UDL_ |----
                     //      |v      case 4
                     //      |----
-                    println!("case 04: Adding bot left of {:?}", pos);
+                    shared::trace!("case 04: Adding bot left of {:?}", pos);
                     *corners += 2;
                     if set_not_contains(right_plot_fences, Dir::Down) {
-                        println!("case 04: Adding bot right of {:?}", pos);
+                        shared::trace!("case 04: Adding bot right of {:?}", pos);
                         *corners += 1;
                     }
                 } else if dir_in(&fences_arr, Dir::Right) {
                     // U_LR  |----|
                     //       |   >|  case 5
                     //       |    |
-                    println!("case 05: Adding top right of {:?}", pos);
+                    shared::trace!("case 05: Adding top right of {:?}", pos);
                     *corners += 2;
                     if set_not_contains(bot_plot_fences, Dir::Right) {
-                        println!("case 05: Adding bot right of {:?}", pos);
+                        shared::trace!("case 05: Adding bot right of {:?}", pos);
                         *corners += 1;
                     }
                 }
@@ -245,16 +245,16 @@ impl Garden {
                 // _DL_  |
                 //       |v     case 6
                 //       |----
-                println!("case 06: Adding bot left of {:?}", pos);
+                shared::trace!("case 06: Adding bot left of {:?}", pos);
                 *corners += 2;
                 let top_plot_fences = self.top_plot_fences(pos);
                 if set_not_contains(top_plot_fences, Dir::Left) {
-                    println!("case 06: Adding top left of {:?}", pos);
+                    shared::trace!("case 06: Adding top left of {:?}", pos);
                     *corners += 1;
                 }
                 let right_plot_fences = self.right_plot_fences(pos);
                 if set_not_contains(right_plot_fences, Dir::Down) {
-                    println!("case 06: Adding bot right of {:?}", pos);
+                    shared::trace!("case 06: Adding bot right of {:?}", pos);
                     *corners += 1;
                 }
                 if dir_in(&fences_arr, Dir::Right) {
@@ -262,9 +262,9 @@ impl Garden {
                     //       |   >|  case 7
                     //       |----|
                     *corners += 2;
-                    println!("case 07: Adding bot right of {:?}", pos);
+                    shared::trace!("case 07: Adding bot right of {:?}", pos);
                     if set_not_contains(top_plot_fences, Dir::Right) {
-                        println!("case 07: Adding top right of {:?}", pos);
+                        shared::trace!("case 07: Adding top right of {:?}", pos);
                         *corners += 1;
                     }
                 }
@@ -274,12 +274,12 @@ impl Garden {
                 //       |
                 let top_plot_fences = self.top_plot_fences(pos);
                 if set_not_contains(top_plot_fences, Dir::Left) {
-                    println!("case 08: Adding top left of {:?}", pos);
+                    shared::trace!("case 08: Adding top left of {:?}", pos);
                     *corners += 1;
                 }
                 let bot_plot_fences = self.bot_plot_fences(pos);
                 if set_not_contains(bot_plot_fences, Dir::Left) {
-                    println!("case 08: Adding bot left of {:?}", pos);
+                    shared::trace!("case 08: Adding bot left of {:?}", pos);
                     *corners += 1;
                 }
                 if dir_in(&fences_arr, Dir::Right) {
@@ -287,11 +287,11 @@ impl Garden {
                     //       |   >|  case 9
                     //       |    |
                     if set_not_contains(top_plot_fences, Dir::Right) {
-                        println!("case 09: Adding top right of {:?}", pos);
+                        shared::trace!("case 09: Adding top right of {:?}", pos);
                         *corners += 1;
                     }
                     if set_not_contains(bot_plot_fences, Dir::Right) {
-                        println!("case 09: Adding bot right of {:?}", pos);
+                        shared::trace!("case 09: Adding bot right of {:?}", pos);
                         *corners += 1;
                     }
                 }
@@ -301,26 +301,26 @@ impl Garden {
                 // U__R  -----|
                 //           ^|  case 10
                 //            |
-                println!("case 10: Adding top right of {:?}", pos);
+                shared::trace!("case 10: Adding top right of {:?}", pos);
                 *corners += 2;
                 let left_plot_fences = self.left_plot_fences(pos);
                 if set_not_contains(left_plot_fences, Dir::Up) {
-                    println!("case 10: Adding top left of {:?}", pos);
+                    shared::trace!("case 10: Adding top left of {:?}", pos);
                     *corners += 1;
                 }
                 let bot_plot_fences = self.bot_plot_fences(pos);
                 if set_not_contains(bot_plot_fences, Dir::Right) {
-                    println!("case 10: Adding bot right of {:?}", pos);
+                    shared::trace!("case 10: Adding bot right of {:?}", pos);
                     *corners += 1;
                 }
                 if dir_in(&fences_arr, Dir::Down) {
                     // UD_R  -----|
                     //           v|  case 11
                     //       -----|
-                    println!("case 11: Adding bot right of {:?}", pos);
+                    shared::trace!("case 11: Adding bot right of {:?}", pos);
                     *corners += 2;
                     if set_not_contains(left_plot_fences, Dir::Down) {
-                        println!("case 11: Adding bot left of {:?}", pos);
+                        shared::trace!("case 11: Adding bot left of {:?}", pos);
                         *corners += 1;
                     }
                 }
@@ -328,16 +328,16 @@ impl Garden {
                 // _D_R      |
                 //          v|  case 12
                 //       ----|
-                println!("case 12: Adding bot right of {:?}", pos);
+                shared::trace!("case 12: Adding bot right of {:?}", pos);
                 *corners += 2;
                 let left_plot_fences = self.left_plot_fences(pos);
                 if set_not_contains(left_plot_fences, Dir::Down) {
-                    println!("case 12: Adding bot left of {:?}", pos);
+                    shared::trace!("case 12: Adding bot left of {:?}", pos);
                     *corners += 1;
                 }
                 let top_plot_fences = self.top_plot_fences(pos);
                 if set_not_contains(top_plot_fences, Dir::Right) {
-                    println!("case 12: Adding top right of {:?}", pos);
+                    shared::trace!("case 12: Adding top right of {:?}", pos);
                     *corners += 1;
                 }
             } else if fences.len() == 1 {
@@ -346,12 +346,12 @@ impl Garden {
                 //           |
                 let top_plot_fences = self.top_plot_fences(pos);
                 if set_not_contains(top_plot_fences, Dir::Right) {
-                    println!("case 13: Adding top right of {:?}", pos);
+                    shared::trace!("case 13: Adding top right of {:?}", pos);
                     *corners += 1;
                 }
                 let bot_plot_fences = self.bot_plot_fences(pos);
                 if set_not_contains(bot_plot_fences, Dir::Right) {
-                    println!("case 13: Adding bot right of {:?}", pos);
+                    shared::trace!("case 13: Adding bot right of {:?}", pos);
                     *corners += 1;
                 }
             }
@@ -361,12 +361,12 @@ impl Garden {
             //
             let left_plot_fences = self.left_plot_fences(pos);
             if set_not_contains(left_plot_fences, Dir::Up) {
-                println!("case 14: Adding top left of {:?}", pos);
+                shared::trace!("case 14: Adding top left of {:?}", pos);
                 *corners += 1;
             }
             let right_plot_fences = self.right_plot_fences(pos);
             if set_not_contains(right_plot_fences, Dir::Up) {
-                println!("case 14: Adding top right of {:?}", pos);
+                shared::trace!("case 14: Adding top right of {:?}", pos);
                 *corners += 1;
             }
             if dir_in(&fences_arr, Dir::Down) {
@@ -374,11 +374,11 @@ impl Garden {
                 //          v     case 15
                 //       -------
                 if set_not_contains(left_plot_fences, Dir::Down) {
-                    println!("case 15: Adding bot left of {:?}", pos);
+                    shared::trace!("case 15: Adding bot left of {:?}", pos);
                     *corners += 1;
                 }
                 if set_not_contains(right_plot_fences, Dir::Down) {
-                    println!("case 15: Adding bot right of {:?}", pos);
+                    shared::trace!("case 15: Adding bot right of {:?}", pos);
                     *corners += 1;
                 }
             }
@@ -388,62 +388,39 @@ impl Garden {
             //       -------
             let left_plot_fences = self.left_plot_fences(pos);
             if set_not_contains(left_plot_fences, Dir::Down) {
-                println!("case 16: Adding bot left of {:?}", pos);
+                shared::trace!("case 16: Adding bot left of {:?}", pos);
                 *corners += 1;
             }
             let right_plot_fences = self.right_plot_fences(pos);
             if set_not_contains(right_plot_fences, Dir::Down) {
-                println!("case 16: Adding bot right of {:?}", pos);
+                shared::trace!("case 16: Adding bot right of {:?}", pos);
                 *corners += 1;
             }
         }
     }
 
     fn left_plot_fences(&self, pos: Pos2) -> Option<&HashSet<Dir>> {
-        if pos.col > 0 {
-            if self.get_plot_ch(pos) != self.get_plot_ch(pos.left()) {
-                None
-            } else {
-                Some(&self.get_plot(pos.left()).fences)
-            }
-        } else {
-            None
-        }
+        self.same_region_plot_fences(pos, Dir::Left)
     }
 
     fn right_plot_fences(&self, pos: Pos2) -> Option<&HashSet<Dir>> {
-        if pos.col < self.matrix[pos.row].len() - 1 {
-            if self.get_plot_ch(pos) != self.get_plot_ch(pos.right()) {
-                None
-            } else {
-                Some(&self.get_plot(pos.right()).fences)
-            }
-        } else {
-            None
-        }
+        self.same_region_plot_fences(pos, Dir::Right)
     }
 
     fn top_plot_fences(&self, pos: Pos2) -> Option<&HashSet<Dir>> {
-        if pos.row > 0 {
-            if self.get_plot_ch(pos) != self.get_plot_ch(pos.up()) {
-                None
-            } else {
-                Some(&self.get_plot(pos.up()).fences)
-            }
-        } else {
-            None
-        }
+        self.same_region_plot_fences(pos, Dir::Up)
     }
 
     fn bot_plot_fences(&self, pos: Pos2) -> Option<&HashSet<Dir>> {
-        if pos.row < self.matrix.len() - 1 {
-            if self.get_plot_ch(pos) != self.get_plot_ch(pos.down()) {
-                None
-            } else {
-                Some(&self.get_plot(pos.down()).fences)
-            }
-        } else {
+        self.same_region_plot_fences(pos, Dir::Down)
+    }
+
+    fn same_region_plot_fences(&self, pos: Pos2, dir: Dir) -> Option<&HashSet<Dir>> {
+        let other = self.matrix.moved(pos, dir)?;
+        if self.get_plot_ch(pos) != self.get_plot_ch(other) {
             None
+        } else {
+            Some(&self.get_plot(other).fences)
         }
     }
 
@@ -485,11 +462,11 @@ impl Garden {
     }
 
     fn get_plot_mut(&mut self, pos: Pos2) -> &mut Plot {
-        &mut self.matrix[pos.row][pos.col]
+        &mut self.matrix[pos]
     }
 
     fn get_plot(&self, pos: Pos2) -> &Plot {
-        &self.matrix[pos.row][pos.col]
+        &self.matrix[pos]
     }
 
     fn get_plot_fences_cnt(&self, pos: Pos2) -> usize {
@@ -504,37 +481,27 @@ impl Garden {
         self.get_plot(pos).region.is_some()
     }
 
+    /// The neighbouring plots, each with the direction back towards `pos`.
     fn get_neighbours(&self, pos: Pos2) -> Vec<Neighbour> {
-        let mut res = vec![];
-
-        if pos.row > 0 {
-            res.push(Neighbour {
-                pos: pos.moved(Dir::Up),
-                dir: Dir::Down,
-            });
-        }
-
-        if pos.col > 0 {
-            res.push(Neighbour {
-                pos: pos.moved(Dir::Left),
-                dir: Dir::Right,
-            });
-        }
+        self.matrix
+            .neighbours_dirs(pos)
+            .map(|(pos, dir)| Neighbour {
+                pos,
+                dir: dir.opposite(),
+            })
+            .collect()
+    }
+}
 
-        if pos.row < self.matrix.len() - 1 {
-            res.push(Neighbour {
-                pos: pos.moved(Dir::Down),
-                dir: Dir::Up,
-            });
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if pos.col < self.matrix[pos.row].len() - 1 {
-            res.push(Neighbour {
-                pos: pos.moved(Dir::Right),
-                dir: Dir::Left,
-            });
+    shared::sample_tests! {
+        Day12;
+        file "sample_input" {
+            sample_part1: part1 => 1930,
+            sample_part2: part2 => 1206,
         }
-
-        res
     }
 }
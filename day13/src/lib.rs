@@ -1,20 +1,27 @@
-use shared::{determinant, intersect_vecs, Vec2D};
-use std::fs::File;
-use std::io;
-use std::io::Read;
+use shared::number_theory::{cramer, non_negative_solutions};
+use shared::parse::{self, Line};
+use shared::{intersect_vecs, Answer, Error, Solution, Vec2D};
 use std::str::FromStr;
 
 type DataType = i64;
 type Vec2 = Vec2D<DataType>;
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+pub struct Day13;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day13 {
+    type Input = Vec<ClawMachine>;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
 fn calculate_part1(input: &[ClawMachine]) -> DataType {
@@ -35,7 +42,7 @@ fn calculate_part2(input: &[ClawMachine]) -> DataType {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct ClawMachine {
+pub struct ClawMachine {
     button_a: Vec2,
     button_b: Vec2,
     prize: Vec2,
@@ -48,29 +55,12 @@ impl ClawMachine {
     }
 
     fn get_the_only_solution(&self) -> Option<Vec2> {
-        let det = match determinant(self.button_a, self.button_b) {
-            0 => return None,
-            det => det as f64,
-        };
-
-        let coeff_a = determinant(self.prize, self.button_b) as f64 / det;
-        let coeff_b = determinant(self.button_a, self.prize) as f64 / det;
-        if coeff_a.is_sign_negative()
-            || coeff_b.is_sign_negative()
-            || coeff_a.fract() != 0.0
-            || coeff_b.fract() != 0.0
-        {
-            return None;
-        }
-
-        Some(Vec2::new(coeff_a as DataType, coeff_b as DataType))
+        cramer(self.button_a, self.button_b, self.prize).filter(|sol| sol.x >= 0 && sol.y >= 0)
     }
 
     fn get_all_possible_solutions(&self) -> Vec<Vec2> {
-        let x_eq = Diophantine::new(self.button_a.x, self.button_b.x, self.prize.x);
-        let y_eq = Diophantine::new(self.button_a.y, self.button_b.y, self.prize.y);
-        let x_sols = x_eq.all_non_negative_solutions();
-        let y_sols = y_eq.all_non_negative_solutions();
+        let x_sols = non_negative_solutions(self.button_a.x, self.button_b.x, self.prize.x);
+        let y_sols = non_negative_solutions(self.button_a.y, self.button_b.y, self.prize.y);
         intersect_vecs(&x_sols, &y_sols)
     }
 
@@ -87,38 +77,45 @@ fn cost(sol: &Vec2) -> DataType {
     3 * sol.x + sol.y
 }
 
-fn parse_file(file_path: &str) -> io::Result<Vec<ClawMachine>> {
-    let mut file = File::open(file_path)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
+fn parse_input(input: &str) -> shared::Result<Vec<ClawMachine>> {
+    parse::sections(input)
+        .map(|section| section.parse())
+        .collect()
+}
 
-    Ok(contents
-        .split("\n\n")
-        .map(|entry| entry.parse().unwrap())
-        .collect::<Vec<ClawMachine>>())
+fn parse_vec2(line: Line, prefix: &str) -> shared::Result<Vec2> {
+    line.strip_prefix(line.text, prefix)?;
+    let [x, y] = line.ints_n()?;
+    Ok(Vec2 { x, y })
 }
 
-fn parse_vec2(s: &str) -> Vec2 {
-    let parts: Vec<&str> = s.split(", ").collect();
-    let x = parts[0]
-        .trim_matches(|c| c == 'X' || c == '=')
-        .parse()
-        .unwrap();
-    let y = parts[1]
-        .trim_matches(|c| c == 'Y' || c == '=')
-        .parse()
-        .unwrap();
-    Vec2 { x, y }
+/// Like [`parse_vec2`], for a button which has to move the claw forward on both axes.
+fn parse_button(line: Line, prefix: &str) -> shared::Result<Vec2> {
+    let offset = parse_vec2(line, prefix)?;
+    let not_forward =
+        parse::int_parts(line.text).find(|part| part.parse::<DataType>().is_ok_and(|n| n <= 0));
+    match not_forward {
+        Some(part) => Err(line.error(part, "a button has to move the claw forward")),
+        None => Ok(offset),
+    }
 }
 
 impl FromStr for ClawMachine {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines: Vec<&str> = s.lines().collect();
-        let button_a = parse_vec2(lines[0].trim_start_matches("Button A: "));
-        let button_b = parse_vec2(lines[1].trim_start_matches("Button B: "));
-        let prize = parse_vec2(lines[2].trim_start_matches("Prize: "));
+        let mut lines = parse::lines(s);
+        let mut next_line = |line_no: usize, prefix: &str| {
+            lines
+                .next()
+                .ok_or_else(|| Error::parse(line_no, 1, format!("expected a `{prefix}` line")))
+        };
+        let button_a = parse_button(next_line(1, "Button A: ")?, "Button A: ")?;
+        let button_b = parse_button(next_line(2, "Button B: ")?, "Button B: ")?;
+        let prize = parse_vec2(next_line(3, "Prize: ")?, "Prize: ")?;
+        if let Some(line) = lines.next() {
+            return Err(line.error(line.text, "expected a blank line after the prize"));
+        }
         Ok(ClawMachine {
             button_a,
             button_b,
@@ -127,87 +124,23 @@ impl FromStr for ClawMachine {
     }
 }
 
-/**************************************************************/
-/******** Despicable code ahead. Proceed with caution! ********/
-/**************************************************************/
-
-#[derive(Debug, Copy, Clone)]
-struct Diophantine {
-    u: DataType,
-    v: DataType,
-    w: DataType,
-}
-
-impl Diophantine {
-    fn new(u: DataType, v: DataType, w: DataType) -> Self {
-        Self { u, v, w }
-    }
-
-    fn all_non_negative_solutions(self) -> Vec<Vec2> {
-        let mut solutions = vec![];
-
-        if let Some((sol, gcd)) = self.solve() {
-            let step_x = self.v / gcd;
-            let step_y = self.u / gcd;
-
-            let k_min = if step_x > 0 {
-                (-sol.x as f64 / step_x as f64).ceil() as i64
-            } else {
-                (-sol.x as f64 / step_x as f64).floor() as i64
-            };
-
-            let k_max = if step_y > 0 {
-                (sol.y as f64 / step_y as f64).floor() as i64
-            } else {
-                (sol.y as f64 / step_y as f64).ceil() as i64
-            };
-
-            // General solution
-            // x = a + k * step_x
-            // y = b - k * step_y
-
-            for k in k_min..=k_max {
-                let x_k = sol.x + k * step_x;
-                let y_k = sol.y - k * step_y;
-
-                solutions.push(Vec2::new(x_k, y_k));
-            }
-        }
-
-        solutions
-    }
-
-    fn solve(self) -> Option<(Vec2, DataType)> {
-        let (x, y, gcd) = gcd_extended(self.u, self.v);
-        if self.w % gcd != 0 {
-            return None;
-        }
-        let scale = self.w / gcd;
-        let a = x * scale;
-        let b = y * scale;
-        Some((Vec2::new(a, b), gcd))
-    }
-}
-
-fn gcd_extended(a: DataType, b: DataType) -> (DataType, DataType, DataType) {
-    if b == 0 {
-        return (1, 0, a);
-    }
-    let (x1, y1, gcd) = gcd_extended(b, a % b);
-    let x = y1;
-    let y = x1 - (a / b) * y1;
-    (x, y, gcd)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    shared::sample_tests! {
+        Day13;
+        file "small_input" {
+            sample_part1: part1 => 280,
+            sample_part2: part2 => 459236326669_u64,
+        }
+    }
+
     #[test]
     fn test_parse_vec2() {
         let input = "X+94, Y+34";
         let expected = Vec2::new(94, 34);
-        let parsed = parse_vec2(input);
+        let parsed = parse_vec2(Line::new(1, input), "").unwrap();
         assert_eq!(parsed, expected);
     }
 
@@ -224,8 +157,49 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_multiple_claw_machines() -> io::Result<()> {
-        let parsed_machines = parse_file("small_input")?;
+    fn test_parse_errors() {
+        let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\n\
+                     Button A: X+94, Y+34\nButton B: X+2a, Y+67\nPrize: X=8400, Y=5400";
+        assert!(matches!(
+            Day13::parse(input),
+            Err(Error::Parse {
+                line: 6,
+                column: 12,
+                ..
+            })
+        ));
+        assert!(matches!(
+            "Button A: X+94, Y+34".parse::<ClawMachine>(),
+            Err(Error::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            "Button A: X+0, Y+0\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400"
+                .parse::<ClawMachine>(),
+            Err(Error::Parse {
+                line: 1,
+                column: 12,
+                ..
+            })
+        ));
+        assert!(matches!(
+            "Button A: X+94, Y+34\nButton B: X+22, Y-67\nPrize: X=8400, Y=5400"
+                .parse::<ClawMachine>(),
+            Err(Error::Parse {
+                line: 2,
+                column: 18,
+                ..
+            })
+        ));
+        assert!(matches!(
+            "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\nPrize: X=1, Y=1"
+                .parse::<ClawMachine>(),
+            Err(Error::Parse { line: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_multiple_claw_machines() -> shared::Result<()> {
+        let parsed_machines = Day13::parse_file(shared::day_file!("small_input"))?;
 
         let expected = vec![
             ClawMachine {
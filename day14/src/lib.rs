@@ -1,35 +1,44 @@
-use shared::Vec2D;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use shared::parse::{self, Line};
+use shared::{Answer, Error, Grid, Params, Solution, Vec2D};
 
 type DataType = i64;
 type Vec2 = Vec2D<DataType>;
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+pub struct Day14;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day14 {
+    type Input = Room;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> shared::Result<Self::Input> {
+        let room = Room {
+            robots: parse_input(input)?,
+            size: RoomSize::new(params.get_or("width", 101)?, params.get_or("height", 103)?),
+            steps: params.get_or("steps", 100)?,
+        };
+        room.validate()?;
+        Ok(room)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
-fn calculate_part1(input: &[Robot]) -> usize {
-    const STEPS: DataType = 100;
-    const WIDTH: DataType = 101;
-    const HEIGHT: DataType = 103;
-    const ROOM_SIZE: RoomSize = RoomSize::new(WIDTH as DataType, HEIGHT as DataType);
-    let new_spots = calc_new_spots(&input, ROOM_SIZE, STEPS);
-    calc_safety_factor(&new_spots, ROOM_SIZE)
+fn calculate_part1(input: &Room) -> usize {
+    let new_spots = calc_new_spots(&input.robots, input.size, input.steps);
+    calc_safety_factor(&new_spots, input.size)
 }
 
-fn calculate_part2(input: &[Robot]) -> usize {
-    const WIDTH: DataType = 101;
-    const HEIGHT: DataType = 103;
-    const ROOM_SIZE: RoomSize = RoomSize::new(WIDTH as DataType, HEIGHT as DataType);
-    commence_manual_labour(input, ROOM_SIZE)
+fn calculate_part2(input: &Room) -> shared::Result<DataType> {
+    find_christmas_tree(&input.robots, input.size)
 }
 
 fn calc_new_spots(robots: &[Robot], room_size: Vec2, steps: DataType) -> Vec<Vec2> {
@@ -40,9 +49,7 @@ fn calc_new_spots(robots: &[Robot], room_size: Vec2, steps: DataType) -> Vec<Vec
 }
 
 fn calc_safety_factor(robots: &[Vec2], room_size: Vec2) -> usize {
-    quadrants_cnt(robots, room_size)
-        .iter()
-        .fold(1, |lhs, rhs| lhs * rhs)
+    quadrants_cnt(robots, room_size).iter().product()
 }
 
 fn quadrants_cnt(robots: &[Vec2], room_size: Vec2) -> Vec<usize> {
@@ -68,124 +75,102 @@ fn quadrants_cnt(robots: &[Vec2], room_size: Vec2) -> Vec<usize> {
 
 type RoomSize = Vec2;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-struct Robot {
-    p: Vec2,
-    v: Vec2,
+/// The robots together with the room they move in and for how long part 1 lets them move.
+pub struct Room {
+    robots: Vec<Robot>,
+    size: RoomSize,
+    steps: DataType,
 }
 
-impl Robot {
-    fn step(self, room_size: RoomSize, steps: DataType) -> Vec2 {
-        let mut new_pos = self.p + (self.v * steps);
-        new_pos %= room_size;
-        if new_pos.x < 0 {
-            new_pos.x += room_size.x;
+impl Room {
+    fn validate(&self) -> shared::Result<()> {
+        if self.size.x <= 0 || self.size.y <= 0 {
+            return Err(Error::InvalidArgument(format!(
+                "`width` and `height` must be positive, got {}x{}",
+                self.size.x, self.size.y
+            )));
         }
-        if new_pos.y < 0 {
-            new_pos.y += room_size.y;
+        if self.steps < 0 {
+            return Err(Error::InvalidArgument(format!(
+                "`steps` can't be negative, got {}",
+                self.steps
+            )));
         }
-        new_pos
+        Ok(())
     }
 }
 
-fn commence_manual_labour(input: &[Robot], room_size: Vec2) -> usize {
-    let mut sec = 1_usize;
-    loop {
-        let positions = calc_new_spots(&input, room_size, sec as DataType);
-        let may_be_tree = print_matrix(&positions, room_size);
-        println!("{}", '-'.to_string().repeat(room_size.x as usize));
-        if !may_be_tree {
-            sec += 1;
-            continue;
-        }
-
-        println!("Do you see a christmas tree [y(es), n(ext) = default]?");
-        let mut buf = String::new();
-        let _ = std::io::stdin().read_line(&mut buf);
-        if buf.starts_with('y') {
-            println!("Yay a christmas tree!");
-            break;
-        }
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Robot {
+    p: Vec2,
+    v: Vec2,
+}
 
-        sec += 1;
+impl Robot {
+    fn step(self, room_size: RoomSize, steps: DataType) -> Vec2 {
+        (self.p + self.v * steps).rem_euclid(room_size)
     }
-    sec
 }
 
-fn parse_file(file_path: &str) -> io::Result<Vec<Robot>> {
-    let path = Path::new(file_path);
-    let file = File::open(&path)?;
-    let reader = io::BufReader::new(file);
-
-    let mut robots = Vec::new();
-
-    for line in reader.lines() {
-        let line = line?;
-        if let Some(robot) = parse_line(&line) {
-            robots.push(robot);
+/// The first second at which the robots may be drawing a christmas tree, the room of that second
+/// is logged for checking it by eye.
+fn find_christmas_tree(input: &[Robot], room_size: Vec2) -> shared::Result<DataType> {
+    // Every robot is back where it started after `width * height` seconds
+    let period = room_size.x * room_size.y;
+    for sec in 1..=period {
+        let positions = calc_new_spots(input, room_size, sec);
+        let may_be_tree = print_matrix(&positions, room_size);
+        shared::trace!("{}", '-'.to_string().repeat(room_size.x as usize));
+        if may_be_tree {
+            shared::info!("A christmas tree may be drawn after {sec} seconds");
+            return Ok(sec);
         }
     }
-
-    Ok(robots)
+    Err(Error::InvalidInput(format!(
+        "No christmas tree within {period} seconds, after which the robots repeat"
+    )))
 }
 
-fn parse_line(line: &str) -> Option<Robot> {
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    if parts.len() != 2 {
-        return None;
-    }
-
-    let p = parse_vec2(parts[0])?;
-    let v = parse_vec2(parts[1])?;
-
-    Some(Robot { p, v })
+fn parse_input(input: &str) -> shared::Result<Vec<Robot>> {
+    parse::parse_lines(input, parse_line)
 }
 
-fn parse_vec2(part: &str) -> Option<Vec2> {
-    let values: Vec<&str> = part[2..].split(',').collect(); // Skips "p=" and "v="
-    if values.len() != 2 {
-        return None;
-    }
-
-    let x = values[0].parse::<DataType>().ok()?;
-    let y = values[1].parse::<DataType>().ok()?;
-
-    Some(Vec2::new(x, y))
+fn parse_line(line: Line) -> shared::Result<Robot> {
+    let [px, py, vx, vy] = line.ints_n()?;
+    Ok(Robot {
+        p: Vec2::new(px, py),
+        v: Vec2::new(vx, vy),
+    })
 }
 
 fn print_matrix(positions: &[Vec2], room_size: Vec2) -> bool {
     let mut tree_pos = None;
-    let mut matrix = vec![vec![' '; room_size.x as usize]; room_size.y as usize];
+    let size = room_size.try_cast::<usize>().expect("the room has a size");
+    let mut matrix = Grid::new(size.x, size.y, ' ');
     for pos in positions {
-        matrix[pos.y as usize][pos.x as usize] = 'X';
-        if may_contain_christmas_tree(&matrix, *pos) {
+        let cell = pos.try_cast().expect("robots stay inside the room");
+        matrix[cell] = 'X';
+        if may_contain_christmas_tree(&matrix, cell) {
             tree_pos = Some(pos);
-            matrix[pos.y as usize][pos.x as usize] = 'O';
+            matrix[cell] = 'O';
         }
     }
 
-    for line in matrix {
-        for ch in line {
-            print!("{ch}");
-        }
-        println!();
-    }
+    shared::trace!("{}", matrix.to_string().trim_end());
 
     match tree_pos {
         Some(pos) => {
-            println!("The christmas tree might be at position: {:?}", pos);
+            shared::trace!("The christmas tree might be at position: {:?}", pos);
             true
         }
         None => false,
     }
 }
 
-fn may_contain_christmas_tree(matrix: &[Vec<char>], pos: Vec2) -> bool {
-    const TREE_HEIGHT_MIN: DataType = 10;
+fn may_contain_christmas_tree(matrix: &Grid<char>, pos: Vec2D<usize>) -> bool {
+    const TREE_HEIGHT_MIN: usize = 10;
     if pos.y > TREE_HEIGHT_MIN {
-        (0..TREE_HEIGHT_MIN)
-            .into_iter()
-            .all(|i| matrix[(pos.y - i) as usize][pos.x as usize] != ' ')
+        (0..TREE_HEIGHT_MIN).all(|i| matrix[Vec2D::new(pos.x, pos.y - i)] != ' ')
     } else {
         false
     }
@@ -195,6 +180,39 @@ fn may_contain_christmas_tree(matrix: &[Vec<char>], pos: Vec2) -> bool {
 mod tests {
     use super::*;
 
+    shared::sample_tests! {
+        Day14;
+        file "sample_input" with (width = 11, height = 7) {
+            sample_part1: part1 => 12,
+        }
+    }
+
+    #[test]
+    fn no_christmas_tree_in_the_sample() {
+        let input = std::fs::read_to_string(shared::day_file!("sample_input")).unwrap();
+        let params = Params::new().with("width", 11).with("height", 7);
+        let room = Day14::parse_with(&input, &params).unwrap();
+        assert!(matches!(
+            calculate_part2(&room),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn room_size_must_be_positive() {
+        let input = std::fs::read_to_string(shared::day_file!("sample_input")).unwrap();
+        for (width, height, steps) in [(0, 7, 100), (11, -7, 100), (11, 7, -1)] {
+            let params = Params::new()
+                .with("width", width)
+                .with("height", height)
+                .with("steps", steps);
+            assert!(matches!(
+                Day14::parse_with(&input, &params),
+                Err(Error::InvalidArgument(_))
+            ));
+        }
+    }
+
     #[test]
     fn test_parse_file() {
         let expected_output = vec![
@@ -248,7 +266,9 @@ mod tests {
             },
         ];
 
-        let result = parse_file("sample_input").expect("Failed to parse file");
-        assert_eq!(result, expected_output);
+        let result =
+            Day14::parse_file(shared::day_file!("sample_input")).expect("Failed to parse file");
+        assert_eq!(result.robots, expected_output);
+        assert_eq!(result.size, RoomSize::new(101, 103));
     }
 }
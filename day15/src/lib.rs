@@ -1,41 +1,45 @@
-use shared::{print_2d_map, Dir, Vec2D};
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-use std::io::BufReader;
+use shared::{Answer, Dir, Error, Grid, Solution, Vec2D};
 
 type DataType = usize;
 type Vec2 = Vec2D<DataType>;
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+pub struct Day15;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day15 {
+    type Input = Warehouse;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
 fn calculate_part1(input: &Warehouse) -> DataType {
     let mut warehouse = input.clone();
-    print_2d_map(&warehouse.map);
+    shared::trace!("{}", warehouse.map);
     while warehouse.simulate_step() {}
-    print_2d_map(&warehouse.map);
+    shared::trace!("{}", warehouse.map);
     warehouse.sum_box_gps_coords()
 }
 
 fn calculate_part2(input: &Warehouse) -> DataType {
     let mut warehouse = input.make_doubled();
-    print_2d_map(&warehouse.map);
+    shared::trace!("{}", warehouse.map);
     while warehouse.simulate_step() {}
-    print_2d_map(&warehouse.map);
+    shared::trace!("{}", warehouse.map);
     warehouse.sum_box_gps_coords()
 }
 
 #[derive(Clone)]
-struct Warehouse {
-    map: Vec<Vec<char>>,
+pub struct Warehouse {
+    map: Grid<char>,
     robot: Vec2,
     robot_moves: Vec<Dir>,
     curr_step: usize,
@@ -48,21 +52,18 @@ impl Warehouse {
         }
 
         let dir = self.robot_moves[self.curr_step];
-        let wanted_robot_pos = self.robot.moved(dir);
-
-        match self.map[wanted_robot_pos.y][wanted_robot_pos.x] {
-            '.' => self.move_robot_free(wanted_robot_pos),
-            'O' => {
-                if self.try_move_box(wanted_robot_pos, dir) {
+        if let Some(wanted_robot_pos) = self.step_from(self.robot, dir) {
+            let wanted_ch = self.map[wanted_robot_pos];
+            match wanted_ch {
+                '.' => self.move_robot_free(wanted_robot_pos),
+                'O' if self.try_move_box(wanted_robot_pos, dir) => {
                     self.move_robot_free(wanted_robot_pos)
                 }
-            }
-            '[' | ']' => {
-                if self.try_move_box_doubled(wanted_robot_pos, dir) {
+                '[' | ']' if self.try_move_box_doubled(wanted_robot_pos, dir) => {
                     self.move_robot_free(wanted_robot_pos)
                 }
+                _ => (),
             }
-            _ => (),
         }
 
         //print_map(&self.map);
@@ -73,62 +74,60 @@ impl Warehouse {
     }
 
     fn sum_box_gps_coords(&self) -> usize {
-        let mut sum = 0_usize;
-        for (y, line) in self.map.iter().enumerate() {
-            for (x, &ch) in line.iter().enumerate() {
-                if ch == 'O' || ch == '[' {
-                    sum += self.gps_coord(Vec2::new(x, y));
-                }
-            }
-        }
-        sum
+        self.map
+            .iter()
+            .filter(|(_, &ch)| ch == 'O' || ch == '[')
+            .map(|(pos, _)| self.gps_coord(pos))
+            .sum()
     }
 
     fn make_doubled(&self) -> Warehouse {
-        let mut map = vec![];
+        let mut rows = vec![];
         let robot = Vec2::new(2 * self.robot.x, self.robot.y);
         let robot_moves = self.robot_moves.clone();
         let curr_step = self.curr_step;
 
-        for line in &self.map {
-            map.push(vec![]);
+        for line in self.map.rows() {
+            rows.push(vec![]);
             for &ch in line {
                 match ch {
-                    '#' => map.last_mut().unwrap().extend(vec!['#', '#']),
-                    'O' => map.last_mut().unwrap().extend(vec!['[', ']']),
-                    '.' => map.last_mut().unwrap().extend(vec!['.', '.']),
-                    '@' => map.last_mut().unwrap().extend(vec!['@', '.']),
+                    '#' => rows.last_mut().unwrap().extend(['#', '#']),
+                    'O' => rows.last_mut().unwrap().extend(['[', ']']),
+                    '.' => rows.last_mut().unwrap().extend(['.', '.']),
+                    '@' => rows.last_mut().unwrap().extend(['@', '.']),
                     _ => (),
                 }
             }
         }
 
         Warehouse {
-            map,
+            map: Grid::from_rows(rows).expect("every row doubles to the same width"),
             robot,
             robot_moves,
             curr_step,
         }
     }
 
+    /// One step from `pos` towards `dir`, `None` past the edge of the map like into a wall.
+    fn step_from(&self, pos: Vec2, dir: Dir) -> Option<Vec2> {
+        pos.moved_within(dir, self.map.size())
+    }
+
     fn try_move_box(&mut self, box_pos: Vec2, dir: Dir) -> bool {
-        let wanted_pos = box_pos.moved(dir);
-        match self.map[wanted_pos.y][wanted_pos.x] {
+        let Some(wanted_pos) = self.step_from(box_pos, dir) else {
+            return false;
+        };
+        let wanted_ch = self.map[wanted_pos];
+        match wanted_ch {
             '.' => self.move_box_free(box_pos, wanted_pos),
-            'O' => {
-                if self.try_move_box(wanted_pos, dir) {
-                    self.move_box_free(box_pos, wanted_pos)
-                } else {
-                    false
-                }
-            }
+            'O' if self.try_move_box(wanted_pos, dir) => self.move_box_free(box_pos, wanted_pos),
             _ => false,
         }
     }
 
     fn try_move_box_doubled(&mut self, box_pos: Vec2, dir: Dir) -> bool {
         let lhs = {
-            let box_side = self.map[box_pos.y][box_pos.x];
+            let box_side = self.map[box_pos];
             if box_side == '[' {
                 box_pos
             } else {
@@ -144,62 +143,69 @@ impl Warehouse {
         }
     }
 
-    fn can_move_box_doubled(&mut self, lhs: Vec2, dir: Dir) -> bool {
+    /// The cells a box pushed towards `dir` moves into, `None` when it would leave the map.
+    fn box_front(&self, lhs: Vec2, dir: Dir) -> Option<Vec<Vec2>> {
         let rhs = lhs.right();
-        let pos_to_check = match dir {
-            Dir::Left => vec![lhs.moved(dir)],
-            Dir::Right => vec![rhs.moved(dir)],
-            Dir::Up | Dir::Down => vec![lhs.moved(dir), rhs.moved(dir)],
+        let sides = match dir {
+            Dir::Left => vec![lhs],
+            Dir::Right => vec![rhs],
+            Dir::Up | Dir::Down => vec![lhs, rhs],
         };
-
-        let can_move = pos_to_check
+        sides
             .into_iter()
-            .all(|pos| match self.map[pos.y][pos.x] {
-                '.' => true,
-                '[' => self.can_move_box_doubled(pos, dir),
-                ']' => self.can_move_box_doubled(pos.left(), dir),
-                _ => false,
-            });
-
-        can_move
+            .map(|side| self.step_from(side, dir))
+            .collect()
     }
 
-    fn move_box_doubled(&mut self, lhs: Vec2, dir: Dir) {
-        let rhs = lhs.right();
-        let pos_to_check = match dir {
-            Dir::Left => vec![lhs.moved(dir)],
-            Dir::Right => vec![rhs.moved(dir)],
-            Dir::Up | Dir::Down => vec![lhs.moved(dir), rhs.moved(dir)],
+    fn can_move_box_doubled(&mut self, lhs: Vec2, dir: Dir) -> bool {
+        let Some(pos_to_check) = self.box_front(lhs, dir) else {
+            return false;
         };
 
+        pos_to_check.into_iter().all(|pos| match self.map[pos] {
+            '.' => true,
+            '[' => self.can_move_box_doubled(pos, dir),
+            ']' => self.can_move_box_doubled(pos.left(), dir),
+            _ => false,
+        })
+    }
+
+    fn move_box_doubled(&mut self, lhs: Vec2, dir: Dir) {
+        let pos_to_check = self
+            .box_front(lhs, dir)
+            .expect("only boxes that can move are moved");
+
         for &pos in &pos_to_check {
-            match self.map[pos.y][pos.x] {
+            match self.map[pos] {
                 '[' => self.move_box_doubled(pos, dir),
                 ']' => self.move_box_doubled(pos.left(), dir),
                 _ => (),
             };
         }
 
-        self.move_box_doubled_free(lhs, lhs.moved(dir));
+        let new_lhs = self
+            .step_from(lhs, dir)
+            .expect("only boxes that can move are moved");
+        self.move_box_doubled_free(lhs, new_lhs);
     }
 
     fn move_box_doubled_free(&mut self, lhs: Vec2, new_lhs: Vec2) {
         let (rhs, new_rhs) = (lhs.right(), new_lhs.right());
-        self.map[lhs.y][lhs.x] = '.';
-        self.map[rhs.y][rhs.x] = '.';
-        self.map[new_lhs.y][new_lhs.x] = '[';
-        self.map[new_rhs.y][new_rhs.x] = ']';
+        self.map[lhs] = '.';
+        self.map[rhs] = '.';
+        self.map[new_lhs] = '[';
+        self.map[new_rhs] = ']';
     }
 
     fn move_robot_free(&mut self, new_pos: Vec2) {
-        self.map[new_pos.y][new_pos.x] = '@';
-        self.map[self.robot.y][self.robot.x] = '.';
+        self.map[new_pos] = '@';
+        self.map[self.robot] = '.';
         self.robot = new_pos;
     }
 
     fn move_box_free(&mut self, old_pos: Vec2, new_pos: Vec2) -> bool {
-        self.map[new_pos.y][new_pos.x] = 'O';
-        self.map[old_pos.y][old_pos.x] = '.';
+        self.map[new_pos] = 'O';
+        self.map[old_pos] = '.';
         true
     }
 
@@ -208,17 +214,14 @@ impl Warehouse {
     }
 }
 
-fn parse_file(file_path: &str) -> io::Result<Warehouse> {
-    let file = File::open(file_path)?;
-    let reader = BufReader::new(file);
-
+fn parse_input(input: &str) -> shared::Result<Warehouse> {
     let mut map = Vec::new();
     let mut robot_position = None;
     let mut robot_moves = Vec::new();
 
     let mut parsing_map = true;
 
-    for line in reader.lines().filter_map(Result::ok) {
+    for (idx, line) in input.lines().enumerate() {
         if parsing_map && (line.is_empty() || !line.starts_with('#')) {
             parsing_map = false;
         }
@@ -233,20 +236,21 @@ fn parse_file(file_path: &str) -> io::Result<Warehouse> {
             }
             map.push(row);
         } else {
-            robot_moves.extend(line.chars().filter_map(Dir::from_char));
+            for (col, ch) in line.chars().enumerate() {
+                let dir = Dir::from_char(ch).ok_or_else(|| {
+                    Error::parse(idx + 1, col + 1, format!("unknown robot move `{ch}`"))
+                })?;
+                robot_moves.push(dir);
+            }
         }
     }
 
-    if robot_position.is_none() {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            "No robot position found",
-        ));
-    }
+    let robot_position =
+        robot_position.ok_or_else(|| Error::InvalidInput("No robot position found".to_string()))?;
 
     Ok(Warehouse {
-        map,
-        robot: robot_position.unwrap(),
+        map: Grid::from_rows(map)?,
+        robot: robot_position,
         robot_moves,
         curr_step: 0,
     })
@@ -256,14 +260,29 @@ fn parse_file(file_path: &str) -> io::Result<Warehouse> {
 mod tests {
     use super::*;
 
+    shared::sample_tests! {
+        Day15;
+        file "sample_input" {
+            sample_part1: part1 => 10092,
+            sample_part2: part2 => 9021,
+        }
+        text "########\n#..O.O.#\n##@.O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#\n########\n\n<^^>>>vv<v>>v<<\n" {
+            small_sample_part1: part1 => 2028,
+        }
+        text "#######\n#...#.#\n#.....#\n#..OO@#\n#..O..#\n#.....#\n#######\n\n<vv<<^^<<^^\n" {
+            small_sample_part2: part2 => 618,
+        }
+    }
+
     #[test]
     fn test_parse_file() {
-        let warehouse = parse_file("sample_input").expect("Failed to parse warehouse");
+        let warehouse = Day15::parse_file(shared::day_file!("sample_input"))
+            .expect("Failed to parse warehouse");
 
         assert_eq!(warehouse.robot, Vec2::new(4, 4));
-        assert_eq!(warehouse.map[4][4], '@');
-        assert_eq!(warehouse.map.len(), 10);
-        assert_eq!(warehouse.map[0].len(), 10);
+        assert_eq!(warehouse.map[Vec2::new(4, 4)], '@');
+        assert_eq!(warehouse.map.height(), 10);
+        assert_eq!(warehouse.map.width(), 10);
 
         assert_eq!(warehouse.robot_moves.len(), 700);
 
@@ -1,48 +1,48 @@
-use shared::{parse_2d_map, Dir, Vec2D};
-use std::{
-    collections::{BinaryHeap, HashMap, HashSet},
-    io,
-};
+use shared::search::{self, Paths};
+use shared::{Answer, Dir, Error, Grid, Solution, Vec2D};
+use std::collections::HashSet;
 use strum::IntoEnumIterator;
 
 type Vec2 = Vec2D<usize>;
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+pub struct Day16;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day16 {
+    type Input = Maze;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
 fn calculate_part1(input: &Maze) -> usize {
-    let (dists, _) = calc_min_price_to_end(&input);
-    min_from_all_dirs(&dists, input.target_pos)
+    let paths = calc_min_price_to_end(input);
+    min_from_all_dirs(&paths, input.target_pos).expect("parsing checks that `E` is reachable")
 }
 
 fn calculate_part2(input: &Maze) -> usize {
-    let (dists, parents) = calc_min_price_to_end(&input);
-    let min_dist = min_from_all_dirs(&dists, input.target_pos);
-    count_best_spots(&parents, input.target_pos, &dists, min_dist)
+    let paths = calc_min_price_to_end(input);
+    let min_dist =
+        min_from_all_dirs(&paths, input.target_pos).expect("parsing checks that `E` is reachable");
+    count_best_spots(&paths, input.target_pos, min_dist)
 }
 
-fn min_from_all_dirs(dists: &HashMap<CellDir, usize>, pos: Vec2) -> usize {
-    let start = CellDir::new(pos, Dir::Right);
-    let mut min = dists[&start];
-    for dir in Dir::iter() {
-        let cell_dir = CellDir::new(pos, dir);
-        let curr_dist = dists[&cell_dir];
-        if curr_dist < min {
-            min = curr_dist;
-        }
-    }
-    min
+fn min_from_all_dirs(paths: &Paths<CellDir>, pos: Vec2) -> Option<usize> {
+    Dir::iter()
+        .filter_map(|dir| paths.dist(&CellDir::new(pos, dir)))
+        .min()
 }
 
-struct Maze {
-    map: Vec<Vec<char>>,
+pub struct Maze {
+    map: Grid<char>,
     pos: Vec2,
     target_pos: Vec2,
 }
@@ -53,106 +53,27 @@ struct CellDir {
     dir: Dir,
 }
 
-fn count_best_spots(
-    parents: &HashMap<CellDir, Vec<CellDir>>,
-    end_pos: Vec2,
-    dists: &HashMap<CellDir, usize>,
-    min_dist: usize,
-) -> usize {
-    let mut visited_cells = HashSet::new();
-    for dir in Dir::iter() {
-        let cell_dir = CellDir::new(end_pos, dir);
-        let curr_dist = dists[&cell_dir];
-        if curr_dist != min_dist {
-            continue;
-        }
-        let mut visited = HashSet::new();
-        count_best_spots_rec(parents, cell_dir, &mut visited, &mut visited_cells);
-    }
-    visited_cells.len()
+fn count_best_spots(paths: &Paths<CellDir>, end_pos: Vec2, min_dist: usize) -> usize {
+    let best_ends = Dir::iter()
+        .map(|dir| CellDir::new(end_pos, dir))
+        .filter(|cell_dir| paths.dist(cell_dir) == Some(min_dist));
+    let best_cells: HashSet<Vec2> = paths
+        .on_shortest_paths(best_ends)
+        .into_iter()
+        .map(|cell_dir| cell_dir.pos)
+        .collect();
+    best_cells.len()
 }
 
-fn count_best_spots_rec(
-    parents: &HashMap<CellDir, Vec<CellDir>>,
-    cell_dir: CellDir,
-    visited: &mut HashSet<CellDir>,
-    visited_cells: &mut HashSet<Vec2>,
-) {
-    visited_cells.insert(cell_dir.pos);
-
-    if !visited.insert(cell_dir) {
-        return;
-    }
-
-    if let Some(curr_parents) = parents.get(&cell_dir) {
-        for &parent in curr_parents {
-            count_best_spots_rec(parents, parent, visited, visited_cells);
-        }
-    }
-}
-
-fn calc_min_price_to_end(maze: &Maze) -> (HashMap<CellDir, usize>, HashMap<CellDir, Vec<CellDir>>) {
-    let mut distances = HashMap::new();
-    let mut parents = HashMap::new();
-    let mut heap = BinaryHeap::new();
-
-    // push all non-wall cells
-    for (y, line) in maze.map.iter().enumerate() {
-        for (x, &ch) in line.iter().enumerate() {
-            if ch == '#' {
-                continue;
-            }
-            for dir in Dir::iter() {
-                distances.insert(CellDir::new(Vec2::new(x, y), dir), usize::MAX);
-            }
-        }
-    }
-
-    // start 0 dist
-    let start_cell_dir = CellDir::new(maze.pos, Dir::Right);
-    if let Some(dist) = distances.get_mut(&start_cell_dir) {
-        *dist = 0;
-    }
-
-    // push start in heap
-    heap.push(MinPriorityPos {
-        pos: maze.pos,
-        dir: Dir::Right,
-        dist: 0,
-    });
-
-    while let Some(curr) = heap.pop() {
-        let curr_cell_dir = CellDir::new(curr.pos, curr.dir);
-        if curr.dist > distances[&curr_cell_dir] {
-            continue;
-        }
-
-        let neighbours = gen_neighbours(&maze.map, &curr);
-        for neighbour in neighbours {
-            let neighbour_cell_dir = CellDir::new(neighbour.pos, neighbour.dir);
-            let this_dist = neighbour.dist;
-            let other_dist = distances[&neighbour_cell_dir];
-
-            if this_dist < other_dist {
-                distances.insert(neighbour_cell_dir, neighbour.dist);
-                parents.insert(neighbour_cell_dir, vec![curr_cell_dir]);
-                heap.push(neighbour);
-            }
-
-            if this_dist == other_dist {
-                let neigh_parents = parents.entry(neighbour_cell_dir).or_default();
-                neigh_parents.push(curr_cell_dir);
-            }
-        }
-    }
-
-    (distances, parents)
+fn calc_min_price_to_end(maze: &Maze) -> Paths<CellDir> {
+    let start = CellDir::new(maze.pos, Dir::Right);
+    search::dijkstra([start], |&cell| gen_neighbours(&maze.map, cell))
 }
 
-fn gen_neighbours(map: &[Vec<char>], cell: &MinPriorityPos) -> Vec<MinPriorityPos> {
+fn gen_neighbours(map: &Grid<char>, cell: CellDir) -> Vec<(CellDir, usize)> {
     let mut neighbours = vec![];
 
-    if map[cell.pos.y][cell.pos.x] == 'E' {
+    if map[cell.pos] == 'E' {
         return neighbours;
     }
 
@@ -162,96 +83,74 @@ fn gen_neighbours(map: &[Vec<char>], cell: &MinPriorityPos) -> Vec<MinPriorityPo
         cell.dir.rotated_90_cw(),
     );
 
-    let (forward_pos, left_pos, right_pos) = (
-        cell.pos.moved(forward_dir),
-        cell.pos.moved(left_dir),
-        cell.pos.moved(right_dir),
-    );
-
-    let (forward_ch, left_ch, right_ch) = (
-        map[forward_pos.y][forward_pos.x],
-        map[left_pos.y][left_pos.x],
-        map[right_pos.y][right_pos.x],
-    );
+    let open = |dir: Dir| {
+        cell.pos
+            .moved_within(dir, map.size())
+            .filter(|&pos| map[pos] != '#')
+    };
 
-    if forward_ch != '#' {
-        neighbours.push(MinPriorityPos {
-            pos: forward_pos,
-            dir: forward_dir,
-            dist: cell.dist + 1,
-        });
+    if let Some(forward_pos) = open(forward_dir) {
+        neighbours.push((CellDir::new(forward_pos, forward_dir), 1));
     }
 
-    if left_ch != '#' {
-        neighbours.push(MinPriorityPos {
-            pos: cell.pos,
-            dir: left_dir,
-            dist: cell.dist + 1000,
-        });
+    if open(left_dir).is_some() {
+        neighbours.push((CellDir::new(cell.pos, left_dir), 1000));
     }
 
-    if right_ch != '#' {
-        neighbours.push(MinPriorityPos {
-            pos: cell.pos,
-            dir: right_dir,
-            dist: cell.dist + 1000,
-        });
+    if open(right_dir).is_some() {
+        neighbours.push((CellDir::new(cell.pos, right_dir), 1000));
     }
 
     neighbours
 }
 
-#[derive(Copy, Clone)]
-struct MinPriorityPos {
-    pos: Vec2,
-    dist: usize,
-    dir: Dir,
-}
-
-impl Eq for MinPriorityPos {}
-
-impl PartialEq for MinPriorityPos {
-    fn eq(&self, other: &Self) -> bool {
-        self.pos.eq(&other.pos)
-    }
-}
-
-impl Ord for MinPriorityPos {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.dist.cmp(&self.dist)
-    }
-}
-
-impl PartialOrd for MinPriorityPos {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
 impl CellDir {
     fn new(pos: Vec2, dir: Dir) -> Self {
         Self { pos, dir }
     }
 }
 
-fn parse_file(file_path: &str) -> io::Result<Maze> {
-    let map = parse_2d_map(file_path)?;
-    let mut start = Vec2::new(1, 1);
-    let mut target = Vec2::new(1, 1);
+fn parse_input(input: &str) -> shared::Result<Maze> {
+    let map = Grid::parse(input)?;
+    let find = |tile: char| {
+        map.find(&tile)
+            .ok_or_else(|| Error::InvalidInput(format!("No `{tile}` tile found")))
+    };
+    let (pos, target_pos) = (find('S')?, find('E')?);
 
-    for (y, line) in map.iter().enumerate() {
-        for (x, ch) in line.iter().enumerate() {
-            match ch {
-                'S' => start = Vec2::new(x, y),
-                'E' => target = Vec2::new(x, y),
-                _ => (),
-            }
-        }
+    let reachable = search::bfs([pos], |&cell: &Vec2| {
+        map.neighbours(cell)
+            .filter(|&next| map[next] != '#')
+            .collect::<Vec<_>>()
+    });
+    if reachable.dist(&target_pos).is_none() {
+        return Err(Error::InvalidInput("No path from `S` to `E`".to_string()));
     }
 
     Ok(Maze {
+        pos,
+        target_pos,
         map,
-        pos: start,
-        target_pos: target,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    shared::sample_tests! {
+        Day16;
+        file "sample_input" {
+            sample_part1: part1 => 7036,
+            sample_part2: part2 => 45,
+        }
+    }
+
+    #[test]
+    fn unreachable_end_is_rejected() {
+        assert!(matches!(
+            Day16::parse("#####\n#S#E#\n#####\n"),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+}
@@ -1,35 +1,43 @@
 use num::FromPrimitive;
+use shared::log::Verbosity;
+use shared::parse::{self, Line};
+use shared::{Answer, Error, Solution};
 use std::collections::VecDeque;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
 
 type Dt = i128;
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+pub struct Day17;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day17 {
+    type Input = Computer;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
 fn calculate_part1(input: &Computer) -> String {
     let mut computer = input.clone();
-    computer.compute(true);
+    computer.compute(shared::log::enabled(Verbosity::Trace));
     computer.get_output()
 }
 
-fn calculate_part2(input: &Computer) -> Dt {
+fn calculate_part2(input: &Computer) -> shared::Result<Dt> {
     let mut computer = input.clone();
     computer.reg_a = 0;
     computer.find_a_output_self()
 }
 
 #[derive(Debug, Clone)]
-struct Computer {
+pub struct Computer {
     reg_a: Dt,
     reg_b: Dt,
     reg_c: Dt,
@@ -39,13 +47,13 @@ struct Computer {
 }
 
 impl Computer {
-    fn compute(&mut self, verbose: bool) {
-        if verbose {
-            self.print();
+    fn compute(&mut self, trace: bool) {
+        if trace {
+            self.trace();
         }
         while self.do_next_instruction() {
-            if verbose {
-                self.print();
+            if trace {
+                self.trace();
             }
         }
     }
@@ -58,7 +66,7 @@ impl Computer {
             .join(",")
     }
 
-    fn find_a_output_self(&self) -> Dt {
+    fn find_a_output_self(&self) -> shared::Result<Dt> {
         let mut queue = VecDeque::new();
         let mut start_comp = self.clone();
         start_comp.reg_a = 0;
@@ -66,7 +74,7 @@ impl Computer {
 
         while let Some((comp, depth)) = queue.pop_front() {
             if depth >= self.program.len() {
-                return comp.reg_a;
+                return Ok(comp.reg_a);
             }
 
             for new_pos in comp.get_all_that_output_self() {
@@ -74,7 +82,9 @@ impl Computer {
             }
         }
 
-        0
+        Err(Error::InvalidInput(
+            "No value of register A makes the program output itself".to_string(),
+        ))
     }
 
     fn literal(&self, operand: u8) -> Dt {
@@ -87,7 +97,7 @@ impl Computer {
             4 => self.reg_a,
             5 => self.reg_b,
             6 => self.reg_c,
-            _ => unreachable!("FIRE!!!"),
+            _ => unreachable!("parsing rejects the reserved combo operand 7"),
         }
     }
 
@@ -130,7 +140,7 @@ impl Computer {
 
     fn do_dv(&mut self, reg: char) {
         match reg {
-            'a' => self.reg_a = self.reg_a / 2_i128.pow(self.combo_op().try_into().unwrap()),
+            'a' => self.reg_a /= 2_i128.pow(self.combo_op().try_into().unwrap()),
             'b' => self.reg_b = self.reg_a / 2_i128.pow(self.combo_op().try_into().unwrap()),
             'c' => self.reg_c = self.reg_a / 2_i128.pow(self.combo_op().try_into().unwrap()),
             _ => unreachable!(),
@@ -143,7 +153,7 @@ impl Computer {
     }
 
     fn do_bxl(&mut self) {
-        self.reg_b = self.reg_b ^ self.lit_op();
+        self.reg_b ^= self.lit_op();
         self.ip += 2;
     }
 
@@ -162,7 +172,7 @@ impl Computer {
     }
 
     fn do_bxc(&mut self) {
-        self.reg_b = self.reg_b ^ self.reg_c;
+        self.reg_b ^= self.reg_c;
         self.ip += 2;
     }
 
@@ -179,14 +189,14 @@ impl Computer {
         self.do_dv('c');
     }
 
-    fn print(&self) {
-        println!("A: {}", self.reg_a);
-        println!("B: {}", self.reg_b);
-        println!("C: {}", self.reg_c);
-        println!("out: {:?}", self.out);
-        println!("{:?}", self.program);
-        println!("{}^", " ".repeat(1 + self.ip * 3));
-        println!("-----------------------------------------------------------");
+    fn trace(&self) {
+        shared::trace!("A: {}", self.reg_a);
+        shared::trace!("B: {}", self.reg_b);
+        shared::trace!("C: {}", self.reg_c);
+        shared::trace!("out: {:?}", self.out);
+        shared::trace!("{:?}", self.program);
+        shared::trace!("{}^", " ".repeat(1 + self.ip * 3));
+        shared::trace!("-----------------------------------------------------------");
     }
 
     fn get_all_that_output_self(&self) -> Vec<Computer> {
@@ -237,40 +247,27 @@ mod instr {
 
 use instr::Instruction;
 
-fn parse_file(file_path: &str) -> io::Result<Computer> {
-    let path = Path::new(file_path);
-    let file = File::open(&path)?;
-    let reader = io::BufReader::new(file);
-
+fn parse_input(input: &str) -> shared::Result<Computer> {
     let mut register_a = 0;
     let mut register_b = 0;
     let mut register_c = 0;
     let mut program = Vec::new();
 
-    for line in reader.lines() {
-        let line = line?;
-        if line.starts_with("Register A:") {
-            if let Some(value) = line.split(":").nth(1) {
-                register_a = value.trim().parse::<Dt>().unwrap_or(0);
-            }
-        } else if line.starts_with("Register B:") {
-            if let Some(value) = line.split(":").nth(1) {
-                register_b = value.trim().parse::<Dt>().unwrap_or(0);
-            }
-        } else if line.starts_with("Register C:") {
-            if let Some(value) = line.split(":").nth(1) {
-                register_c = value.trim().parse::<Dt>().unwrap_or(0);
-            }
-        } else if line.starts_with("Program:") {
-            if let Some(values) = line.split(":").nth(1) {
-                program = values
-                    .split(',')
-                    .filter_map(|v| v.trim().parse::<u8>().ok())
-                    .collect();
-            }
+    for line in parse::non_empty_lines(input) {
+        let (key, value) = line.key_value()?;
+        match key {
+            "Register A" => register_a = line.field(value)?,
+            "Register B" => register_b = line.field(value)?,
+            "Register C" => register_c = line.field(value)?,
+            "Program" => program = parse_program(line, value)?,
+            _ => return Err(line.error(key, format!("unexpected `{key}`"))),
         }
     }
 
+    if program.is_empty() {
+        return Err(Error::InvalidInput("No program found".to_string()));
+    }
+
     Ok(Computer {
         reg_a: register_a,
         reg_b: register_b,
@@ -280,3 +277,74 @@ fn parse_file(file_path: &str) -> io::Result<Computer> {
         out: vec![],
     })
 }
+
+/// The 3-bit values of the program, which has to be made of whole instructions with valid
+/// operands so that it can be run without any further checks.
+fn parse_program(line: Line, value: &str) -> shared::Result<Vec<u8>> {
+    let parts: Vec<_> = value.split(',').collect();
+    let program = parts
+        .iter()
+        .map(|&v| match line.field(v)? {
+            op @ 0..=7 => Ok(op),
+            op => Err(line.error(v, format!("`{op}` is not a 3-bit number"))),
+        })
+        .collect::<shared::Result<Vec<u8>>>()?;
+
+    if program.len() % 2 != 0 {
+        return Err(line.error(value, "expected pairs of an instruction and an operand"));
+    }
+
+    for (idx, pair) in program.chunks(2).enumerate() {
+        let operand_part = parts[idx * 2 + 1];
+        let instruction: Instruction =
+            FromPrimitive::from_u8(pair[0]).expect("3-bit numbers are instructions");
+        match (instruction, pair[1]) {
+            (
+                Instruction::Adv
+                | Instruction::Bst
+                | Instruction::Out
+                | Instruction::Bdv
+                | Instruction::Cdv,
+                7,
+            ) => {
+                return Err(line.error(operand_part, "`7` is not a valid combo operand"));
+            }
+            (Instruction::Jnz, target) if target % 2 != 0 => {
+                return Err(line.error(operand_part, "jumps must land on an instruction"));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    shared::sample_tests! {
+        Day17;
+        file "sample_input" {
+            sample_part1: part1 => "4,6,3,5,6,3,5,2,1,0",
+        }
+    }
+
+    #[test]
+    fn invalid_programs_are_rejected() {
+        let column_of_error = |program: &str| match Day17::parse(program) {
+            Err(Error::Parse { column, .. }) => column,
+            res => panic!("expected a parse error, got {res:?}"),
+        };
+        assert_eq!(column_of_error("Program: 0,7,5"), 10);
+        assert_eq!(column_of_error("Program: 0,7,5,4"), 12);
+        assert_eq!(column_of_error("Program: 0,1,3,1"), 16);
+        assert!(Day17::parse("Register A: 1").is_err());
+    }
+
+    #[test]
+    fn quine_without_solution() {
+        let computer = Day17::parse("Program: 1,7,5,5").unwrap();
+        assert!(calculate_part2(&computer).is_err());
+    }
+}
@@ -1,95 +1,198 @@
-use shared::Vec2D;
-use std::collections::VecDeque;
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-use std::path::Path;
+use shared::{parse_field, search, Answer, Error, Grid, Params, Solution, Vec2D};
+use std::collections::HashSet;
 
 type Vec2 = Vec2D<usize>;
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+pub struct Day18;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
-}
+impl Solution for Day18 {
+    type Input = MemorySpace;
 
-fn calculate_part1(input: &[Vec2]) -> usize {
-    const WIDTH: usize = 71_usize;
-    const HEIGHT: usize = 71_usize;
-    const BYTES: usize = 1024_usize;
-    let map = gen_2d_map(input, WIDTH, HEIGHT, BYTES);
-    shortest_path(&map).unwrap()
-}
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> shared::Result<Self::Input> {
+        let space = MemorySpace {
+            bytes: parse_input(input)?,
+            width: params.get_or("width", 71)?,
+            height: params.get_or("height", 71)?,
+            fallen: params.get_or("bytes", 1024)?,
+        };
+        space.validate()?;
+        Ok(space)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
 
-fn calculate_part2(input: &[Vec2]) -> String {
-    const WIDTH: usize = 71_usize;
-    const HEIGHT: usize = 71_usize;
-    const BYTES: usize = 1024_usize;
-    let mut map = gen_2d_map(input, WIDTH, HEIGHT, BYTES);
-    let mut bytes = BYTES;
-    while shortest_path(&map).is_some() {
-        let byte = input[bytes];
-        map[byte.y][byte.x] = '#';
-        bytes += 1;
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
     }
-    let byte = input[bytes - 1];
-    format!("{},{}", byte.x, byte.y)
 }
 
-fn shortest_path(map: &[Vec<char>]) -> Option<usize> {
-    let height = map.len();
-    let width = map[0].len();
-    let limit = Vec2::new(width, height);
-    let target = Vec2::new(width - 1, height - 1);
-    let mut visited = vec![vec![false; width]; height];
-    let mut queue = VecDeque::new();
+/// The falling bytes, the size of the memory space and how many bytes have fallen for part 1.
+pub struct MemorySpace {
+    bytes: Vec<Vec2>,
+    width: usize,
+    height: usize,
+    fallen: usize,
+}
 
-    queue.push_back((Vec2::new(0, 0), 0));
-    visited[0][0] = true;
+impl MemorySpace {
+    /// Checks that `fallen` is within the input and that every byte lands inside the space and on
+    /// a spot no other byte fell on.
+    fn validate(&self) -> shared::Result<()> {
+        if !(1..=self.bytes.len()).contains(&self.fallen) {
+            return Err(Error::InvalidArgument(format!(
+                "`bytes` must be between 1 and the {} bytes of the input, got {}",
+                self.bytes.len(),
+                self.fallen
+            )));
+        }
 
-    while let Some((pos, distance)) = queue.pop_front() {
-        if pos == target {
-            return Some(distance);
+        if let Some(byte) = self
+            .bytes
+            .iter()
+            .find(|byte| byte.x >= self.width || byte.y >= self.height)
+        {
+            return Err(Error::InvalidInput(format!(
+                "byte {},{} falls outside of the {}x{} space",
+                byte.x, byte.y, self.width, self.height
+            )));
         }
 
-        for new_pos in pos.gen_neighbours_constrained(limit) {
-            if !visited[new_pos.y][new_pos.x] && map[new_pos.y][new_pos.x] == '.' {
-                visited[new_pos.y][new_pos.x] = true;
-                queue.push_back((new_pos, distance + 1));
-            }
+        let mut seen = HashSet::new();
+        if let Some(byte) = self.bytes.iter().find(|&&byte| !seen.insert(byte)) {
+            return Err(Error::InvalidInput(format!(
+                "byte {},{} falls more than once",
+                byte.x, byte.y
+            )));
         }
+        Ok(())
     }
+}
+
+fn calculate_part1(input: &MemorySpace) -> shared::Result<usize> {
+    let map = gen_2d_map(&input.bytes, input.width, input.height, input.fallen);
+    shortest_path(&map).ok_or_else(|| {
+        Error::InvalidInput(format!(
+            "The exit can't be reached after {} bytes",
+            input.fallen
+        ))
+    })
+}
+
+fn calculate_part2(input: &MemorySpace) -> shared::Result<String> {
+    let byte = first_blocking_byte(&input.bytes, input.width, input.height, input.fallen)
+        .ok_or_else(|| {
+            Error::InvalidInput("The exit is still reachable after all bytes fell".to_string())
+        })?;
+    Ok(format!("{},{}", byte.x, byte.y))
+}
 
-    None
+fn first_blocking_byte(input: &[Vec2], width: usize, height: usize, bytes: usize) -> Option<Vec2> {
+    // The exit is usually still reachable after the first `bytes`, so those needn't be tried
+    let mut map = gen_2d_map(input, width, height, bytes);
+    let skipped = if shortest_path(&map).is_some() {
+        bytes
+    } else {
+        map = gen_2d_map(input, width, height, 0);
+        0
+    };
+    input[skipped..].iter().copied().find(|&byte| {
+        map[byte] = '#';
+        shortest_path(&map).is_none()
+    })
 }
 
-fn gen_2d_map(input: &[Vec2], width: usize, height: usize, bytes_cnt: usize) -> Vec<Vec<char>> {
-    let mut map = vec![vec!['.'; width]; height];
-    for byte in input.iter().take(bytes_cnt) {
-        map[byte.y][byte.x] = '#';
+fn shortest_path(map: &Grid<char>) -> Option<usize> {
+    let target = Vec2::new(map.width() - 1, map.height() - 1);
+    let neighbours = |&pos: &Vec2| {
+        map.neighbours(pos)
+            .filter(|&new_pos| map[new_pos] == '.')
+            .map(|new_pos| (new_pos, 1))
+    };
+    let (_, distance) = search::astar(
+        Vec2::new(0, 0),
+        neighbours,
+        |&pos| pos.manhattan_distance(target),
+        |&pos| pos == target,
+    )?;
+    Some(distance)
+}
+
+fn gen_2d_map(input: &[Vec2], width: usize, height: usize, bytes_cnt: usize) -> Grid<char> {
+    let mut map = Grid::new(width, height, '.');
+    for &byte in input.iter().take(bytes_cnt) {
+        map[byte] = '#';
     }
     map
 }
 
-fn parse_file(file_path: &str) -> io::Result<Vec<Vec2>> {
-    let path = Path::new(file_path);
-    let file = File::open(&path)?;
-    let reader = io::BufReader::new(file);
-
-    let pairs = reader
+fn parse_input(input: &str) -> shared::Result<Vec<Vec2>> {
+    input
         .lines()
-        .map(|line| {
-            let line = line.unwrap();
-            let mut parts = line.split(',');
-            let x = parts.next().unwrap().trim().parse().unwrap();
-            let y = parts.next().unwrap().trim().parse().unwrap();
-            Vec2::new(x, y)
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            let (x, y) = line
+                .split_once(',')
+                .ok_or_else(|| Error::parse(idx + 1, 1, "expected `<x>,<y>`"))?;
+            Ok(Vec2::new(
+                parse_field(idx + 1, line, x)?,
+                parse_field(idx + 1, line, y)?,
+            ))
         })
-        .collect();
+        .collect()
+}
 
-    Ok(pairs)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    shared::sample_tests! {
+        Day18;
+        file "sample_input" with (width = 7, height = 7, bytes = 12) {
+            sample_part1: part1 => 22,
+            sample_part2: part2 => "6,1",
+        }
+    }
+
+    #[test]
+    fn unanswerable_parts_are_errors() {
+        let input = "0,1\n1,0\n1,1";
+        let params = Params::new()
+            .with("width", 2)
+            .with("height", 2)
+            .with("bytes", 2);
+        let space = Day18::parse_with(input, &params).unwrap();
+        assert!(calculate_part1(&space).is_err());
+        assert_eq!(calculate_part2(&space).unwrap(), "1,0");
+
+        let params = Params::new()
+            .with("width", 3)
+            .with("height", 3)
+            .with("bytes", 1);
+        let space = Day18::parse_with("1,1", &params).unwrap();
+        assert_eq!(calculate_part1(&space).unwrap(), 4);
+        assert!(calculate_part2(&space).is_err());
+        assert!(Day18::parse_with("1,1\n1,1", &params).is_err());
+    }
+
+    #[test]
+    fn fallen_bytes_out_of_range() {
+        let input = std::fs::read_to_string(shared::day_file!("sample_input")).unwrap();
+        for bytes in [0, 26] {
+            let params = Params::new()
+                .with("width", 7)
+                .with("height", 7)
+                .with("bytes", bytes);
+            assert!(matches!(
+                Day18::parse_with(&input, &params),
+                Err(Error::InvalidArgument(_))
+            ));
+        }
+    }
 }
@@ -1,16 +1,22 @@
+use shared::{Answer, Error, Solution};
 use std::collections::{BTreeMap, HashMap};
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
-
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
-
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = InputData;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
 fn calculate_part1(input: &InputData) -> usize {
@@ -151,18 +157,17 @@ impl TowelTree {
 }
 
 #[derive(Debug)]
-struct InputData {
+pub struct InputData {
     towels: Vec<Vec<char>>,
     patterns: Vec<Vec<char>>,
 }
 
-fn parse_file(file_path: &str) -> io::Result<InputData> {
-    let file = File::open(file_path)?;
-    let reader = io::BufReader::new(file);
+fn parse_input(input: &str) -> shared::Result<InputData> {
+    let mut lines_iter = input.lines();
 
-    let mut lines_iter = reader.lines();
-
-    let towels_line = lines_iter.next().unwrap()?;
+    let towels_line = lines_iter
+        .next()
+        .ok_or_else(|| Error::InvalidInput("No towels found".to_string()))?;
     let towels: Vec<Vec<_>> = towels_line
         .split(", ")
         .map(|s| s.chars().collect())
@@ -171,9 +176,20 @@ fn parse_file(file_path: &str) -> io::Result<InputData> {
     // Skip the empty line
     lines_iter.next();
 
-    let patterns: Vec<Vec<_>> = lines_iter
-        .map(|line| line.unwrap().chars().collect())
-        .collect();
+    let patterns: Vec<Vec<_>> = lines_iter.map(|line| line.chars().collect()).collect();
 
     Ok(InputData { towels, patterns })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    shared::sample_tests! {
+        Day19;
+        file "sample_input" {
+            sample_part1: part1 => 6,
+            sample_part2: part2 => 16,
+        }
+    }
+}
@@ -1,31 +1,56 @@
-use shared::{parse_2d_map, Vec2D};
+use shared::{search, Answer, Error, Grid, Params, Solution, Vec2D};
 use std::collections::HashMap;
-use std::collections::VecDeque;
-use std::io;
 
 type Vec2 = Vec2D<usize>;
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    let res_part2 = calculate_part2(&input);
+pub struct Day20;
 
-    println!("Part one result: {res_part1}");
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day20 {
+    type Input = Racetrack;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> shared::Result<Self::Input> {
+        let map = Grid::parse(input)?;
+        for tile in ['S', 'E'] {
+            if map.find::<Vec2>(&tile).is_none() {
+                return Err(Error::InvalidInput(format!("No `{tile}` tile found")));
+            }
+        }
+        Ok(Racetrack {
+            map,
+            min_saves: params.get_or("min_saves", 100)?,
+        })
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
+}
+
+/// The racetrack map and the least picoseconds a cheat has to save to be counted.
+pub struct Racetrack {
+    map: Grid<char>,
+    min_saves: usize,
 }
 
-fn calculate_part1(input: &[Vec<char>]) -> usize {
-    let dists = dists_from_start(input);
-    find_cheats(&dists, 2)
+fn calculate_part1(input: &Racetrack) -> usize {
+    let dists = dists_from_start(&input.map);
+    find_cheats(&dists, 2, input.min_saves)
 }
 
-fn calculate_part2(input: &[Vec<char>]) -> usize {
-    let dists = dists_from_start(input);
-    find_cheats(&dists, 20)
+fn calculate_part2(input: &Racetrack) -> usize {
+    let dists = dists_from_start(&input.map);
+    find_cheats(&dists, 20, input.min_saves)
 }
 
-fn find_cheats(dists: &HashMap<Vec2, usize>, no_clip_time: usize) -> usize {
+fn find_cheats(dists: &HashMap<Vec2, usize>, no_clip_time: usize, min_saves: usize) -> usize {
     let mut cnt = 0;
     for (&pos1, &dist1) in dists {
         for (&pos2, &dist2) in dists {
@@ -36,7 +61,7 @@ fn find_cheats(dists: &HashMap<Vec2, usize>, no_clip_time: usize) -> usize {
             let dist = pos1.manhattan_distance(pos2);
             if dist <= no_clip_time {
                 let saves = dist1.abs_diff(dist2) - dist;
-                if saves >= 100 {
+                if saves >= min_saves {
                     cnt += 1;
                 }
             }
@@ -46,58 +71,35 @@ fn find_cheats(dists: &HashMap<Vec2, usize>, no_clip_time: usize) -> usize {
     cnt / 2 // each cheat was counted twice
 }
 
-fn dists_from_start(map: &[Vec<char>]) -> HashMap<Vec2, usize> {
+fn dists_from_start(map: &Grid<char>) -> HashMap<Vec2, usize> {
     let start = get_start(map);
     let end = get_end(map);
-    let mut distances = HashMap::new();
-
-    let height = map.len();
-    let width = map[0].len();
-    let limit = Vec2::new(width, height);
-    let mut visited = vec![vec![false; width]; height];
-    let mut queue = VecDeque::new();
-
-    queue.push_back((start, 0));
-    visited[start.y][start.x] = true;
-
-    while let Some((pos, distance)) = queue.pop_front() {
-        distances.insert(pos, distance);
-
-        if pos == end {
-            continue;
-        }
-
-        for new_pos in pos.gen_neighbours_constrained(limit) {
-            if !visited[new_pos.y][new_pos.x] && map[new_pos.y][new_pos.x] != '#' {
-                visited[new_pos.y][new_pos.x] = true;
-                queue.push_back((new_pos, distance + 1));
-            }
-        }
-    }
-
-    distances
+    let neighbours = |&pos: &Vec2| {
+        let track = map.neighbours(pos).filter(|&new_pos| map[new_pos] != '#');
+        track.filter(move |_| pos != end)
+    };
+    search::bfs([start], neighbours).into_dists()
 }
 
-fn search_for(map: &[Vec<char>], target: char) -> Option<Vec2> {
-    for (y, line) in map.iter().enumerate() {
-        for (x, &ch) in line.iter().enumerate() {
-            if ch == target {
-                return Some(Vec2::new(x, y));
-            }
-        }
-    }
-
-    None
+fn get_start(map: &Grid<char>) -> Vec2 {
+    map.find(&'S').unwrap()
 }
 
-fn get_start(map: &[Vec<char>]) -> Vec2 {
-    search_for(map, 'S').unwrap()
+fn get_end(map: &Grid<char>) -> Vec2 {
+    map.find(&'E').unwrap()
 }
 
-fn get_end(map: &[Vec<char>]) -> Vec2 {
-    search_for(map, 'E').unwrap()
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-fn parse_file(file_path: &str) -> io::Result<Vec<Vec<char>>> {
-    parse_2d_map(file_path)
+    shared::sample_tests! {
+        Day20;
+        file "sample_input" with (min_saves = 20) {
+            sample_part1: part1 => 5,
+        }
+        file "sample_input" with (min_saves = 50) {
+            sample_part2: part2 => 285,
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use lazy_static::lazy_static;
-use shared::{parse_2d_map, Vec2D};
-use std::collections::{HashMap, VecDeque};
-use std::{io, usize};
+use shared::parse::{self, Line};
+use shared::{search, Answer, Grid, Solution, Vec2D};
+use std::collections::HashMap;
 
 type Vec2 = Vec2D<usize>;
 type PathsMap = HashMap<FromTo, Vec<CharPath>>;
@@ -9,35 +9,38 @@ type Code = [char; 4];
 type CharPath = Vec<char>;
 type MemoMap = HashMap<(Vec<char>, usize), usize>;
 
-static DIR_PAD: &[&[char]] = &[&['#', '^', 'A'], &['<', 'v', '>']];
-static NUM_PAD: &[&[char]] = &[
-    &['7', '8', '9'],
-    &['4', '5', '6'],
-    &['1', '2', '3'],
-    &['#', '0', 'A'],
-];
+static DIR_PAD: &str = "#^A\n<v>";
+static NUM_PAD: &str = "789\n456\n123\n#0A";
 
 lazy_static! {
-    static ref DIR_PAD_PATHS: PathsMap = shortest_paths(DIR_PAD);
-    static ref NUM_PAD_PATHS: PathsMap = shortest_paths(NUM_PAD);
+    static ref DIR_PAD_PATHS: PathsMap = shortest_paths(&Grid::parse(DIR_PAD).unwrap());
+    static ref NUM_PAD_PATHS: PathsMap = shortest_paths(&Grid::parse(NUM_PAD).unwrap());
 }
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    println!("Part one result: {res_part1}");
+pub struct Day21;
 
-    let res_part2 = calculate_part2(&input);
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day21 {
+    type Input = Vec<Code>;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
 fn calculate_part1(input: &[Code]) -> usize {
-    Historians::new(&input, 2).control()
+    Historians::new(input, 2).control()
 }
 
 fn calculate_part2(input: &[Code]) -> usize {
-    Historians::new(&input, 25).control()
+    Historians::new(input, 25).control()
 }
 
 struct Historians {
@@ -100,7 +103,7 @@ impl Pads {
         let mut least_len = usize::MAX;
 
         for path in num_paths {
-            let dir_path_min_len = self.get_min_dir_depth_first(&path, self.dir_pads_cnt, memo);
+            let dir_path_min_len = self.get_min_dir_depth_first(path, self.dir_pads_cnt, memo);
             if dir_path_min_len < least_len {
                 least_len = dir_path_min_len;
             }
@@ -127,7 +130,7 @@ impl Pads {
             let child_paths = &DIR_PAD_PATHS[&from_to];
             let mut child_least_len = usize::MAX;
             for path in child_paths {
-                let child_min = self.get_min_dir_depth_first(&path, depth - 1, memo);
+                let child_min = self.get_min_dir_depth_first(path, depth - 1, memo);
                 if child_min < child_least_len {
                     child_least_len = child_min;
                 }
@@ -153,90 +156,108 @@ impl FromTo {
     }
 }
 
-/// Blatantly obvious AI generated bfs_pahs and shrotest_paths functions
-
 /// Performs a BFS from a given start position and returns all shortest paths as sequences of `Dir`.
-fn bfs_paths(map: &[&[char]], start: Vec2) -> HashMap<Vec2, Vec<CharPath>> {
-    let height = map.len();
-    let width = map[0].len();
-    let limit = Vec2::new(width, height);
-
-    // Track the shortest distance and the paths to each node
-    let mut visited = vec![vec![None; width]; height];
-    let mut queue = VecDeque::new();
-    let mut paths = HashMap::new();
-
-    queue.push_back((start, Vec::new()));
-    visited[start.y][start.x] = Some(0);
-    paths.insert(start, vec![Vec::new()]);
-
-    while let Some((pos, current_path)) = queue.pop_front() {
-        let current_distance = visited[pos.y][pos.x].unwrap();
-
-        // Generate neighbors
-        let ns = pos.gen_neighbours_dirs_constrained(limit);
-        for (new_pos, dir) in ns {
-            if map[new_pos.y][new_pos.x] == '#' {
-                continue;
-            }
-
-            let new_distance = current_distance + 1;
-            let mut new_path = current_path.clone();
-            new_path.push(dir.to_char());
-
-            if visited[new_pos.y][new_pos.x].is_none()
-                || visited[new_pos.y][new_pos.x] == Some(new_distance)
-            {
-                // If it's not visited or if visited at the same distance, add the new path
-                visited[new_pos.y][new_pos.x] = Some(new_distance);
-
-                // Enqueue the position for further exploration if it's the shortest path
-                if visited[new_pos.y][new_pos.x] == Some(new_distance) {
-                    queue.push_back((new_pos, new_path.clone()));
-                    paths.entry(new_pos).or_insert_with(Vec::new).push(new_path);
-                }
-            }
-        }
-    }
+fn bfs_paths(map: &Grid<char>, start: Vec2) -> HashMap<Vec2, Vec<CharPath>> {
+    let paths = search::bfs([start], |&pos| {
+        map.neighbours(pos).filter(|&new_pos| map[new_pos] != '#')
+    });
 
     paths
-        .values_mut()
-        .for_each(|paths| paths.iter_mut().for_each(|path| path.push('A')));
+        .dists()
+        .keys()
+        .map(|&to| {
+            let char_paths = paths
+                .all_paths_to(&to)
+                .iter()
+                .map(|path| to_char_path(map, path))
+                .collect();
+            (to, char_paths)
+        })
+        .collect()
+}
 
-    paths
+/// The directions stepping along `path`, followed by an `A` press.
+fn to_char_path(map: &Grid<char>, path: &[Vec2]) -> CharPath {
+    path.windows(2)
+        .map(|step| {
+            let (_, dir) = map
+                .neighbours_dirs(step[0])
+                .find(|&(pos, _)| pos == step[1])
+                .expect("paths step between neighbours");
+            dir.to_char()
+        })
+        .chain(['A'])
+        .collect()
 }
 
 /// Computes the shortest paths from all positions to all other positions as sequences of `Dir`.
-fn shortest_paths(map: &[&[char]]) -> PathsMap {
-    let height = map.len();
-    let width = map[0].len();
-
+fn shortest_paths(map: &Grid<char>) -> PathsMap {
     let mut paths_map = HashMap::new();
 
-    for start_y in 0..height {
-        for start_x in 0..width {
-            let from = map[start_y][start_x];
-            if from == '#' {
-                continue;
-            }
-
-            let start = Vec2::new(start_x, start_y);
-            let local_paths = bfs_paths(map, start);
+    for (start, &from) in map.iter() {
+        if from == '#' {
+            continue;
+        }
 
-            for (to_pos, paths) in local_paths {
-                let to = map[to_pos.y][to_pos.x];
-                let key = FromTo::new(from, to);
-                paths_map.entry(key).or_insert_with(Vec::new).extend(paths);
-            }
+        for (to_pos, paths) in bfs_paths(map, start) {
+            let to = map[to_pos];
+            let key = FromTo::new(from, to);
+            paths_map.entry(key).or_insert_with(Vec::new).extend(paths);
         }
     }
 
     paths_map
 }
 
-fn parse_file(file_path: &str) -> io::Result<Vec<Code>> {
-    Ok(parse_2d_map(file_path)?
-        .into_iter()
-        .map(|x| x.try_into().unwrap())
-        .collect())
+fn parse_input(input: &str) -> shared::Result<Vec<Code>> {
+    parse::parse_lines(input, parse_code)
+}
+
+/// A code of three digits followed by `A`, like `029A`.
+fn parse_code(line: Line) -> shared::Result<Code> {
+    let text = line.text.trim();
+    for (idx, ch) in text.char_indices() {
+        let valid = if idx < 3 {
+            ch.is_ascii_digit()
+        } else {
+            ch == 'A'
+        };
+        if !valid {
+            let expected = if idx < 3 { "a digit" } else { "`A`" };
+            let part = &text[idx..idx + ch.len_utf8()];
+            return Err(line.error(part, format!("expected {expected}, found `{ch}`")));
+        }
+    }
+
+    let code: Vec<_> = text.chars().collect();
+    code.try_into()
+        .map_err(|_| line.error(text, "expected a code of 4 characters"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    shared::sample_tests! {
+        Day21;
+        file "sample_input" {
+            sample_part1: part1 => 126384,
+            sample_part2: part2 => 154115708116294_u64,
+        }
+    }
+
+    #[test]
+    fn codes_are_three_digits_and_a() {
+        assert!(matches!(
+            Day21::parse("029A\n12ZA"),
+            Err(shared::Error::Parse {
+                line: 2,
+                column: 3,
+                ..
+            })
+        ));
+        assert!(Day21::parse("029").is_err());
+        assert!(Day21::parse("029AA").is_err());
+        assert!(Day21::parse("A29A").is_err());
+    }
 }
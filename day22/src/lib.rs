@@ -1,52 +1,61 @@
-use indicatif::ProgressBar;
-use indicatif::ProgressStyle;
 use rayon::prelude::*;
+use shared::log::Progress;
+use shared::{parse_field, Answer, Params, Solution};
 use std::collections::HashSet;
-use std::fs::File;
-use std::io;
-use std::io::BufRead;
 
 type QuadDeltas = [i64; 4];
-const DEPTH: usize = 2000;
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    println!("Part one result: {res_part1}");
+pub struct Day22;
 
-    let res_part2 = calculate_part2(&input);
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day22 {
+    type Input = Buyers;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> shared::Result<Self::Input> {
+        Ok(Buyers {
+            secrets: parse_input(input)?,
+            depth: params.get_or("depth", 2000)?,
+        })
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
-fn calculate_part1(input: &[u64]) -> u64 {
+/// The initial secret of every buyer and how many new secrets each of them generates.
+pub struct Buyers {
+    secrets: Vec<u64>,
+    depth: usize,
+}
+
+fn calculate_part1(input: &Buyers) -> u64 {
     let vec: Vec<_> = input
+        .secrets
         .iter()
-        .map(|&secret| gen_new_secret(secret, DEPTH))
+        .map(|&secret| gen_new_secret(secret, input.depth))
         .collect();
 
     vec.iter().sum()
 }
 
-fn calculate_part2(input: &[u64]) -> usize {
-    let deltas = gen_quad_deltas(input, DEPTH);
-    let progress_bar = ProgressBar::new(deltas.len() as u64);
+fn calculate_part2(input: &Buyers) -> usize {
+    let deltas = gen_quad_deltas(&input.secrets, input.depth);
+    let progress = Progress::new("day22", deltas.len() as u64);
 
     // Brute-force that mf in parallel, but make it pretty :)
-    progress_bar.set_style(
-        ProgressStyle::default_bar()
-            .template(
-                "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta})",
-            )
-            .unwrap()
-            .progress_chars("#>-"),
-    );
-
     deltas
         .par_iter()
         .map(|&deltas| {
-            progress_bar.inc(1);
-            calc_bananas(input, DEPTH, &deltas)
+            progress.inc();
+            calc_bananas(&input.secrets, input.depth, &deltas)
         })
         .max()
         .unwrap_or(0)
@@ -55,8 +64,7 @@ fn calculate_part2(input: &[u64]) -> usize {
 fn calc_bananas(secrets: &[u64], depth: usize, deltas: &QuadDeltas) -> usize {
     secrets
         .iter()
-        .map(|&secret| buy_bananas(secret, depth, deltas))
-        .filter_map(|x| x)
+        .filter_map(|&secret| buy_bananas(secret, depth, deltas))
         .sum()
 }
 
@@ -88,9 +96,9 @@ fn gen_prices(secret: u64, depth: usize) -> Vec<i64> {
     let mut prev_secret = secret;
     let mut vec = vec![0; depth + 1];
     vec[0] = price(secret);
-    for i in 1..=depth {
+    for price_slot in vec.iter_mut().skip(1) {
         let new_secret = gen_new_secret(prev_secret, 1);
-        vec[i] = price(new_secret);
+        *price_slot = price(new_secret);
         prev_secret = new_secret;
     }
     vec
@@ -136,11 +144,26 @@ fn prune(num: u64) -> u64 {
     num % 16777216
 }
 
-fn parse_file(file_path: &str) -> io::Result<Vec<u64>> {
-    Ok(File::open(file_path)
-        .map(io::BufReader::new)?
+fn parse_input(input: &str) -> shared::Result<Vec<u64>> {
+    input
         .lines()
-        .filter_map(Result::ok)
-        .map(|line| line.trim().parse::<u64>().unwrap())
-        .collect())
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| parse_field(idx + 1, line, line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    shared::sample_tests! {
+        Day22;
+        file "sample_input" {
+            sample_part1: part1 => 37327623,
+        }
+        text "1\n2\n3\n2024\n" {
+            sample_part2: part2 => 23,
+        }
+    }
 }
@@ -1,9 +1,7 @@
+use shared::{Answer, Error, Solution};
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::fs::File;
 use std::hash::Hash;
-use std::io;
-use std::io::BufRead;
 
 type UndirGraph = HashMap<String, HashSet<String>>;
 
@@ -14,14 +12,22 @@ struct Triangle {
     c: String,
 }
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    println!("Part one result: {res_part1}");
+pub struct Day23;
 
-    let res_part2 = calculate_part2(&input);
-    println!("Part two result: {res_part2}");
-    Ok(())
+impl Solution for Day23 {
+    type Input = Vec<(String, String)>;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
 fn calculate_part1(input: &[(String, String)]) -> usize {
@@ -29,9 +35,7 @@ fn calculate_part1(input: &[(String, String)]) -> usize {
     let triangles: HashSet<Triangle> = graph
         .keys()
         .filter(|node| node.starts_with('t'))
-        .map(|node| get_triangles_from(&graph, node))
-        .into_iter()
-        .flat_map(|set| set)
+        .flat_map(|node| get_triangles_from(&graph, node))
         .collect();
     triangles.len()
 }
@@ -40,7 +44,7 @@ fn calculate_part2(input: &[(String, String)]) -> String {
     let graph = to_undir_graph(input);
     let max_clique_size = max_node_degree(&graph);
     let max = find_largest_clique(&graph, max_clique_size);
-    let mut max = max.clone().into_iter().collect::<Vec<_>>();
+    let mut max = max.into_iter().collect::<Vec<_>>();
     max.sort();
     max.join(",")
 }
@@ -92,7 +96,7 @@ fn get_triangles_from(graph: &UndirGraph, node: &str) -> HashSet<Triangle> {
 
 impl Triangle {
     fn new(a: &str, b: &str, c: &str) -> Self {
-        let mut nodes = vec![a, b, c];
+        let mut nodes = [a, b, c];
         nodes.sort();
         Self {
             a: nodes[0].to_string(),
@@ -102,16 +106,18 @@ impl Triangle {
     }
 }
 
-fn parse_file(file_path: &str) -> io::Result<Vec<(String, String)>> {
-    Ok(File::open(file_path)
-        .map(io::BufReader::new)?
+fn parse_input(input: &str) -> shared::Result<Vec<(String, String)>> {
+    input
         .lines()
-        .filter_map(Result::ok)
-        .map(|line| {
-            let (left, right) = line.split_once('-').unwrap();
-            (left.to_string(), right.to_string())
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            let (left, right) = line
+                .split_once('-')
+                .ok_or_else(|| Error::parse(idx + 1, 1, "expected `<computer>-<computer>`"))?;
+            Ok((left.to_string(), right.to_string()))
         })
-        .collect())
+        .collect()
 }
 
 // Blatant AI generated algo :(
@@ -176,3 +182,16 @@ fn bron_kerbosch(
 
     false // Signal to continue recursion
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    shared::sample_tests! {
+        Day23;
+        file "sample_input" {
+            sample_part1: part1 => 7,
+            sample_part2: part2 => "co,de,ka,ta",
+        }
+    }
+}
@@ -1,18 +1,22 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{self, BufRead},
-    path::Path,
-};
-
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    println!("Part one result: {res_part1}");
-
-    let res_part2 = calculate_part2(&input);
-    println!("Part two result: {res_part2}");
-    Ok(())
+use shared::{parse, Answer, Solution};
+use std::collections::HashMap;
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = State;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> impl Answer {
+        calculate_part2(input)
+    }
 }
 
 fn calculate_part1(input: &State) -> u64 {
@@ -26,6 +30,7 @@ fn calculate_part2(input: &State) -> String {
 
 type Wire = String;
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Gate {
     AND,
@@ -40,7 +45,7 @@ struct Connection {
 }
 
 #[derive(Debug, Clone)]
-struct State {
+pub struct State {
     wire_values: HashMap<Wire, bool>,
     connections: HashMap<Wire, Connection>,
 }
@@ -74,18 +79,15 @@ impl State {
             return val;
         }
 
-        match self.connections.get(wire) {
-            None => {
-                unreachable!("Cannot calculate wire {wire} value. It doesn't have required inputs.")
-            }
-            Some(connection) => {
-                let lhs_val = self.calc_wire_val_internal(wire_values, &connection.from.0);
-                let rhs_val = self.calc_wire_val_internal(wire_values, &connection.from.1);
-                let res = connection.gate.calc(lhs_val, rhs_val);
-                wire_values.insert(wire.clone(), res);
-                res
-            }
-        }
+        let connection = self
+            .connections
+            .get(wire)
+            .expect("parsing checks that every wire has a value or a gate");
+        let lhs_val = self.calc_wire_val_internal(wire_values, &connection.from.0);
+        let rhs_val = self.calc_wire_val_internal(wire_values, &connection.from.1);
+        let res = connection.gate.calc(lhs_val, rhs_val);
+        wire_values.insert(wire.clone(), res);
+        res
     }
 
     fn get_broken_outputs(&self) -> Vec<String> {
@@ -102,11 +104,11 @@ impl State {
 
     fn get_broken_full_adder_output(&self, bit: usize) -> Option<String> {
         let zbit = str_bit('z', bit);
-        if let None = &self.connections.get(&zbit) {
+        if !self.connections.contains_key(&zbit) {
             return None;
         }
 
-        if let None = &self.wire_values.get(&str_bit('x', bit)) {
+        if !self.wire_values.contains_key(&str_bit('x', bit)) {
             return None; // Hopefully the erorr isn't in the last bit :P
         }
 
@@ -120,10 +122,15 @@ impl State {
         let prev_xor_wires = get_sorted_froms(prev_xor_con);
 
         match self.check_get_or_from_first_xor(xor_con, bit) {
-            Err(broken_output) => return Some(broken_output),
+            Err(broken_output) => Some(broken_output),
             Ok((or_lhs, or_rhs)) => {
-                let lhs_con = &self.connections[or_lhs];
-                let rhs_con = &self.connections[or_rhs];
+                // The carry is never one of the input wires
+                let Some(lhs_con) = self.connections.get(or_lhs) else {
+                    return Some(or_lhs.to_string());
+                };
+                let Some(rhs_con) = self.connections.get(or_rhs) else {
+                    return Some(or_rhs.to_string());
+                };
                 // Input never has both of these swapped
                 // if lhs_con.gate != Gate::AND && rhs_con.gate != Gate::AND {
                 //     return Some(format!("{or_lhs}&{or_rhs}"));
@@ -140,19 +147,15 @@ impl State {
                 let rhs_or_wires = get_sorted_froms(rhs_con);
 
                 let prev_inputs = (&str_bit('x', bit - 1), &str_bit('y', bit - 1));
-                if lhs_or_wires == prev_xor_wires {
-                    if rhs_or_wires != prev_inputs {
-                        return Some(or_rhs.to_string());
-                    }
+                if lhs_or_wires == prev_xor_wires && rhs_or_wires != prev_inputs {
+                    return Some(or_rhs.to_string());
                 }
 
-                if rhs_or_wires == prev_xor_wires {
-                    if lhs_or_wires != prev_inputs {
-                        return Some(or_lhs.to_string());
-                    }
-                };
+                if rhs_or_wires == prev_xor_wires && lhs_or_wires != prev_inputs {
+                    return Some(or_lhs.to_string());
+                }
 
-                return None;
+                None
             }
         }
     }
@@ -163,8 +166,12 @@ impl State {
         bit: usize,
     ) -> Result<(&String, &String), String> {
         let (xor_lhs, xor_rhs) = get_sorted_froms(first_xor_conn);
-        let lhs_conn = &self.connections[xor_lhs];
-        let rhs_conn = &self.connections[xor_rhs];
+        // The output bit is never computed straight from the input wires
+        let (Some(lhs_conn), Some(rhs_conn)) =
+            (self.connections.get(xor_lhs), self.connections.get(xor_rhs))
+        else {
+            return Err(str_bit('z', bit));
+        };
         let mut or_err = "".to_string();
 
         let mut xor_conn = None;
@@ -178,14 +185,12 @@ impl State {
             }
         }
 
-        if xor_conn.is_none() {
-            if rhs_conn.gate == Gate::XOR {
-                let froms = get_sorted_froms(rhs_conn);
-                if froms.0 == &str_bit('x', bit) || froms.1 == &str_bit('y', bit) {
-                    xor_conn = Some(rhs_conn);
-                    or_conn = Some(lhs_conn);
-                    or_err = xor_lhs.to_string();
-                }
+        if xor_conn.is_none() && rhs_conn.gate == Gate::XOR {
+            let froms = get_sorted_froms(rhs_conn);
+            if froms.0 == &str_bit('x', bit) || froms.1 == &str_bit('y', bit) {
+                xor_conn = Some(rhs_conn);
+                or_conn = Some(lhs_conn);
+                or_err = xor_lhs.to_string();
             }
         }
 
@@ -210,7 +215,7 @@ impl State {
 }
 
 fn get_sorted_froms(conn: &Connection) -> (&String, &String) {
-    let mut froms = vec![&conn.from.0, &conn.from.1];
+    let mut froms = [&conn.from.0, &conn.from.1];
     froms.sort();
     (froms[0], froms[1])
 }
@@ -220,12 +225,12 @@ fn str_bit(ch: char, bit: usize) -> String {
 }
 
 impl Gate {
-    fn from_str(gate_str: &str) -> Gate {
+    fn from_str(gate_str: &str) -> Option<Gate> {
         match gate_str {
-            "AND" => Gate::AND,
-            "OR" => Gate::OR,
-            "XOR" => Gate::XOR,
-            _ => unreachable!("Invalid gate type"),
+            "AND" => Some(Gate::AND),
+            "OR" => Some(Gate::OR),
+            "XOR" => Some(Gate::XOR),
+            _ => None,
         }
     }
 
@@ -238,43 +243,50 @@ impl Gate {
     }
 }
 
-fn parse_file(file_path: &str) -> io::Result<State> {
-    let path = Path::new(file_path);
-    let file = File::open(&path)?;
-    let reader = io::BufReader::new(file);
-
+fn parse_input(input: &str) -> shared::Result<State> {
     let mut wire_values = HashMap::new();
     let mut connections = HashMap::new();
+    let mut gate_inputs = vec![];
 
-    for line in reader.lines() {
-        let line = line?;
-
-        if line.contains(":") {
-            // Parse wire value: "<wire>: <bool>"
-            let parts: Vec<&str> = line.split(':').map(|s| s.trim()).collect();
-            let wire = parts[0].to_string();
-            let value = parts[1] == "1";
-            wire_values.insert(wire, value);
-        } else if line.contains("->") {
+    for line in parse::non_empty_lines(input) {
+        if let Some((lhs, result_wire)) = line.text.split_once("->") {
             // Parse connection: "<from_lhs> <gate> <from_rhs> -> <result>"
-            let parts: Vec<&str> = line.split("->").map(|s| s.trim()).collect();
-            let lhs = parts[0];
-            let result_wire = parts[1].to_string();
-
+            let result_wire = result_wire.trim().to_string();
             let tokens: Vec<&str> = lhs.split_whitespace().collect();
-            let from_wire1 = tokens[0].to_string();
-            let gate = Gate::from_str(tokens[1]);
-            let from_wire2 = tokens[2].to_string();
-
-            assert!(connections
-                .insert(
-                    result_wire,
-                    Connection {
-                        from: (from_wire1, from_wire2),
-                        gate,
-                    },
-                )
-                .is_none());
+            let &[from_wire1, gate, from_wire2] = tokens.as_slice() else {
+                return Err(line.error(line.text, "expected `<wire> <gate> <wire> -> <wire>`"));
+            };
+            let gate = Gate::from_str(gate)
+                .ok_or_else(|| line.error(gate, format!("unknown gate `{gate}`")))?;
+            gate_inputs.extend([(line, from_wire1), (line, from_wire2)]);
+
+            if connections.contains_key(&result_wire) {
+                let message = format!("wire `{result_wire}` already has an input");
+                return Err(line.error(line.text, message));
+            }
+            connections.insert(
+                result_wire,
+                Connection {
+                    from: (from_wire1.to_string(), from_wire2.to_string()),
+                    gate,
+                },
+            );
+        } else {
+            // Parse wire value: "<wire>: <bool>"
+            let (wire, value) = line.key_value()?;
+            let value = match value {
+                "0" => false,
+                "1" => true,
+                _ => return Err(line.error(value, "expected `0` or `1`")),
+            };
+            wire_values.insert(wire.to_string(), value);
+        }
+    }
+
+    for (line, wire) in gate_inputs {
+        if !wire_values.contains_key(wire) && !connections.contains_key(wire) {
+            let message = format!("wire `{wire}` has neither a value nor a gate");
+            return Err(line.error(wire, message));
         }
     }
 
@@ -283,3 +295,29 @@ fn parse_file(file_path: &str) -> io::Result<State> {
         connections,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    shared::sample_tests! {
+        Day24;
+        file "sample_input" {
+            sample_part1: part1 => 4,
+        }
+    }
+
+    #[test]
+    fn undriven_gate_inputs_are_rejected() {
+        assert!(matches!(
+            Day24::parse("x00: 1\ny00: 1\nx00 AND q -> z00"),
+            Err(shared::Error::Parse {
+                line: 3,
+                column: 9,
+                ..
+            })
+        ));
+        let state = Day24::parse("x00: 1\ny00: 1\nx00 AND y00 -> z00").unwrap();
+        assert_eq!(calculate_part1(&state), 1);
+    }
+}
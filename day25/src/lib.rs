@@ -1,13 +1,26 @@
-use std::fs;
-use std::io;
+use shared::parse::{self, Section};
+use shared::{Answer, Params, Solution};
 
-pub fn run() -> io::Result<()> {
-    let input = parse_file("input")?;
-    let res_part1 = calculate_part1(&input);
-    println!("Part one result: {res_part1}");
+pub struct Day25;
 
-    println!("Part two result: No part 2 :O");
-    Ok(())
+impl Solution for Day25 {
+    type Input = Schematics;
+
+    fn parse(input: &str) -> shared::Result<Self::Input> {
+        Self::parse_with(input, &Params::default())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> shared::Result<Self::Input> {
+        parse_input(input, params.get_or("max_height", 7)?)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer {
+        calculate_part1(input)
+    }
+
+    fn part2(_input: &Self::Input) -> impl Answer {
+        "No part 2 :O"
+    }
 }
 
 fn calculate_part1(input: &Schematics) -> usize {
@@ -18,11 +31,12 @@ type Schematic = Vec<Vec<char>>;
 type Heights = Vec<usize>;
 
 #[derive(Debug, Clone)]
-struct Schematics {
+pub struct Schematics {
     keys: Vec<Schematic>,
     locks: Vec<Schematic>,
     keys_heights: Vec<Heights>,
     locks_heights: Vec<Heights>,
+    max_height: usize,
 }
 
 fn is_lock(schematic: &[Vec<char>]) -> bool {
@@ -58,24 +72,37 @@ fn calculate_heights(schematic: &[Vec<char>], is_lock: bool) -> Vec<usize> {
     heights
 }
 
-fn parse_schematic(schematic: &str) -> Vec<Vec<char>> {
-    schematic
-        .lines()
-        .map(|line| line.chars().collect())
+/// The rows of a schematic, which has to be a `max_height` tall rectangle.
+fn parse_schematic(schematic: &Section, max_height: usize) -> shared::Result<Schematic> {
+    let lines = schematic.lines();
+    if lines.len() != max_height {
+        let message = format!("expected {max_height} rows, found {}", lines.len());
+        return Err(schematic.error(message));
+    }
+
+    let width = lines[0].text.chars().count();
+    lines
+        .iter()
+        .map(|line| {
+            if line.text.chars().count() != width {
+                return Err(line.error(line.text, format!("expected {width} columns")));
+            }
+            Ok(line.text.chars().collect())
+        })
         .collect()
 }
 
-fn parse_file(file_path: &str) -> io::Result<Schematics> {
-    let mut res = Schematics::new();
-    let content = fs::read_to_string(file_path)?;
-    let schematics: Vec<&str> = content.split("\n\n").collect();
+fn parse_input(input: &str, max_height: usize) -> shared::Result<Schematics> {
+    let mut res = Schematics::new(max_height);
 
-    for schematic in schematics {
-        let parsed = parse_schematic(schematic);
+    for schematic in parse::sections(input) {
+        let parsed = parse_schematic(&schematic, max_height)?;
         if is_lock(&parsed) {
             res.locks.push(parsed);
         } else if is_key(&parsed) {
             res.keys.push(parsed);
+        } else {
+            return Err(schematic.error("schematic is neither a lock nor a key"));
         }
     }
 
@@ -91,22 +118,22 @@ fn parse_file(file_path: &str) -> io::Result<Schematics> {
 }
 
 impl Schematics {
-    fn new() -> Self {
+    fn new(max_height: usize) -> Self {
         Schematics {
             keys: vec![],
             locks: vec![],
             keys_heights: vec![],
             locks_heights: vec![],
+            max_height,
         }
     }
 
     fn calc_valid_pairs(&self) -> usize {
         let mut valid_pairs = 0;
-        const SCHEM_MAX_HEIGHT: usize = 7;
 
         for lock in &self.locks_heights {
             for key in &self.keys_heights {
-                if can_fit(lock, key, SCHEM_MAX_HEIGHT) {
+                if can_fit(lock, key, self.max_height) {
                     valid_pairs += 1;
                 }
             }
@@ -115,3 +142,28 @@ impl Schematics {
         valid_pairs
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    shared::sample_tests! {
+        Day25;
+        file "sample_input" {
+            sample_part1: part1 => 3,
+        }
+    }
+
+    #[test]
+    fn ragged_schematics_are_rejected() {
+        let params = Params::new().with("max_height", 3);
+        assert!(matches!(
+            Day25::parse_with("#####\n.####\n..", &params),
+            Err(shared::Error::Parse { line: 3, .. })
+        ));
+        assert!(matches!(
+            Day25::parse_with("#####\n.####\n\n.....\n#####", &params),
+            Err(shared::Error::Parse { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,564 @@
+//! Coordinates on the puzzles' maps.
+//!
+//! Both types describe the same plane: row `0` is the top one and column `0` the leftmost,
+//! so `x` is the column and `y` is the row, and going [`Dir::Up`] decreases `row` and `y` alike.
+//! - [`Pos2D`] is a point given as a `row` and a `col`, the way maps are indexed.
+//! - [`Vec2D`] is `x` and `y`, either a point the way the puzzles state them or an offset
+//!   between two points.
+//!
+//! A point plus an offset is a point, and two points subtract to the offset between them.
+//! Conversions between the two always map `row <-> y` and `col <-> x`.
+//!
+//! Coordinates can be signed or unsigned. The plain `up`/`left`/`moved` steps overflow like the
+//! integers they're made of, so where a step could leave the range of the type (like going up
+//! from row `0` of a `Pos2D<usize>`) use one of
+//! - `checked_moved`, which is `None` outside the range of the type,
+//! - `moved_within`, which is `None` outside of `0..bounds`,
+//! - `wrapping_moved`, which wraps around `0..size` like on a torus.
+//!
+//! [`Pos2D::try_cast`] / [`Vec2D::try_cast`] switch between signed and unsigned coordinates.
+
+use derive_more::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use std::fmt::Debug;
+use std::ops::{self, Rem, RemAssign};
+
+use crate::{Dir, Dir8};
+
+/// The integer types coordinates are made of.
+pub trait Coord:
+    Copy
+    + Ord
+    + Debug
+    + ops::Add<Output = Self>
+    + ops::Sub<Output = Self>
+    + ops::Mul<Output = Self>
+    + ops::Div<Output = Self>
+    + ops::Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    <$t>::rem_euclid(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// `value` stepped by `-1`, `0` or `1`, or `None` when that leaves the range of `T`.
+fn checked_step<T: Coord>(value: T, step: i8) -> Option<T> {
+    match step {
+        -1 => value.checked_sub(T::ONE),
+        1 => value.checked_add(T::ONE),
+        _ => Some(value),
+    }
+}
+
+/// `value` stepped by `-1`, `0` or `1` and wrapped into `0..size`.
+fn wrapping_step<T: Coord>(value: T, step: i8, size: T) -> T {
+    let value = value.rem_euclid(size);
+    match step {
+        -1 if value == T::ZERO => size - T::ONE,
+        -1 => value - T::ONE,
+        1 if value + T::ONE == size => T::ZERO,
+        1 => value + T::ONE,
+        _ => value,
+    }
+}
+
+/// `x` and `y`, where `x` is the column and `y` is the row. A point or an offset between points.
+#[derive(
+    Copy,
+    Clone,
+    Hash,
+    Eq,
+    PartialEq,
+    Debug,
+    Add,
+    AddAssign,
+    Sub,
+    SubAssign,
+    Div,
+    DivAssign,
+    Mul,
+    MulAssign,
+)]
+pub struct Vec2D<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point given as a `row` and a `col`.
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
+pub struct Pos2D<T> {
+    pub row: T,
+    pub col: T,
+}
+
+impl<T> Pos2D<T> {
+    pub const fn new(row: T, col: T) -> Self {
+        Self { row, col }
+    }
+
+    /// The same point as `x` (the column) and `y` (the row).
+    pub fn to_vec(self) -> Vec2D<T> {
+        Vec2D {
+            x: self.col,
+            y: self.row,
+        }
+    }
+
+    /// The point with both coordinates converted to `U`, or `None` when one doesn't fit,
+    /// e.g. a negative row of a `Pos2D<i64>` as a `Pos2D<usize>`.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Pos2D<U>> {
+        Some(Pos2D {
+            row: self.row.try_into().ok()?,
+            col: self.col.try_into().ok()?,
+        })
+    }
+}
+
+impl<T: Coord> Pos2D<T> {
+    pub fn up(self) -> Self {
+        Self::new(self.row - T::ONE, self.col)
+    }
+
+    pub fn down(self) -> Self {
+        Self::new(self.row + T::ONE, self.col)
+    }
+
+    pub fn left(self) -> Self {
+        Self::new(self.row, self.col - T::ONE)
+    }
+
+    pub fn right(self) -> Self {
+        Self::new(self.row, self.col + T::ONE)
+    }
+
+    pub fn moved(self, dir: Dir) -> Self {
+        match dir {
+            Dir::Up => self.up(),
+            Dir::Down => self.down(),
+            Dir::Left => self.left(),
+            Dir::Right => self.right(),
+        }
+    }
+
+    /// The offset leading from `self` to `other`.
+    pub fn make_vec_to(self, other: Self) -> Vec2D<T> {
+        other - self
+    }
+
+    /// The point `offset` away, or `None` when it's outside the range of `T`.
+    pub fn checked_add(self, offset: Vec2D<T>) -> Option<Self> {
+        Some(Self::new(
+            self.row.checked_add(offset.y)?,
+            self.col.checked_add(offset.x)?,
+        ))
+    }
+
+    /// The point `offset` back, or `None` when it's outside the range of `T`.
+    pub fn checked_sub(self, offset: Vec2D<T>) -> Option<Self> {
+        Some(Self::new(
+            self.row.checked_sub(offset.y)?,
+            self.col.checked_sub(offset.x)?,
+        ))
+    }
+
+    /// One step towards `dir`, diagonals included, or `None` when it's outside the range of `T`.
+    pub fn checked_moved(self, dir: impl Into<Dir8>) -> Option<Self> {
+        Some(self.to_vec().checked_moved(dir)?.to_pos())
+    }
+
+    /// One step towards `dir`, or `None` when it leaves `0..bounds.y` rows and `0..bounds.x` columns.
+    pub fn moved_within(self, dir: impl Into<Dir8>, bounds: Vec2D<T>) -> Option<Self> {
+        Some(self.to_vec().moved_within(dir, bounds)?.to_pos())
+    }
+
+    /// One step towards `dir` on a torus of `size.y` rows and `size.x` columns,
+    /// leaving one edge comes back in from the opposite one.
+    pub fn wrapping_moved(self, dir: impl Into<Dir8>, size: Vec2D<T>) -> Self {
+        self.to_vec().wrapping_moved(dir, size).to_pos()
+    }
+}
+
+impl<T: ops::Add<Output = T>> ops::Add<Vec2D<T>> for Pos2D<T> {
+    type Output = Self;
+
+    fn add(self, offset: Vec2D<T>) -> Self {
+        Self::new(self.row + offset.y, self.col + offset.x)
+    }
+}
+
+impl<T: ops::AddAssign> ops::AddAssign<Vec2D<T>> for Pos2D<T> {
+    fn add_assign(&mut self, offset: Vec2D<T>) {
+        self.row += offset.y;
+        self.col += offset.x;
+    }
+}
+
+impl<T: ops::Sub<Output = T>> ops::Sub<Vec2D<T>> for Pos2D<T> {
+    type Output = Self;
+
+    fn sub(self, offset: Vec2D<T>) -> Self {
+        Self::new(self.row - offset.y, self.col - offset.x)
+    }
+}
+
+impl<T: ops::Sub<Output = T>> ops::Sub for Pos2D<T> {
+    type Output = Vec2D<T>;
+
+    /// The offset leading from `other` to `self`.
+    fn sub(self, other: Self) -> Vec2D<T> {
+        Vec2D::new(self.col - other.col, self.row - other.row)
+    }
+}
+
+impl<T> Vec2D<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// The point given as a `row` (`y`) and a `col` (`x`).
+    pub fn from_pos(pos: Pos2D<T>) -> Self {
+        pos.to_vec()
+    }
+
+    /// The same point as a `row` (`y`) and a `col` (`x`).
+    pub fn to_pos(self) -> Pos2D<T> {
+        Pos2D::new(self.y, self.x)
+    }
+
+    /// The vector with both coordinates converted to `U`, or `None` when one doesn't fit.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Vec2D<U>> {
+        Some(Vec2D {
+            x: self.x.try_into().ok()?,
+            y: self.y.try_into().ok()?,
+        })
+    }
+}
+
+impl<T> From<Pos2D<T>> for Vec2D<T> {
+    fn from(pos: Pos2D<T>) -> Self {
+        pos.to_vec()
+    }
+}
+
+impl<T> From<Vec2D<T>> for Pos2D<T> {
+    fn from(vec: Vec2D<T>) -> Self {
+        vec.to_pos()
+    }
+}
+
+impl<T: Coord> Vec2D<T> {
+    pub fn up(self) -> Self {
+        Self::new(self.x, self.y - T::ONE)
+    }
+
+    pub fn down(self) -> Self {
+        Self::new(self.x, self.y + T::ONE)
+    }
+
+    pub fn left(self) -> Self {
+        Self::new(self.x - T::ONE, self.y)
+    }
+
+    pub fn right(self) -> Self {
+        Self::new(self.x + T::ONE, self.y)
+    }
+
+    pub fn moved(self, dir: Dir) -> Self {
+        match dir {
+            Dir::Up => self.up(),
+            Dir::Down => self.down(),
+            Dir::Left => self.left(),
+            Dir::Right => self.right(),
+        }
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+        ))
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_sub(other.x)?,
+            self.y.checked_sub(other.y)?,
+        ))
+    }
+
+    /// One step towards `dir`, diagonals included, or `None` when it's outside the range of `T`.
+    pub fn checked_moved(self, dir: impl Into<Dir8>) -> Option<Self> {
+        let delta = dir.into().delta::<i8>();
+        Some(Self::new(
+            checked_step(self.x, delta.x)?,
+            checked_step(self.y, delta.y)?,
+        ))
+    }
+
+    /// One step towards `dir`, or `None` when it leaves `0..bounds` on either axis.
+    pub fn moved_within(self, dir: impl Into<Dir8>, bounds: Self) -> Option<Self> {
+        let moved = self.checked_moved(dir)?;
+        let inside = |value: T, bound: T| T::ZERO <= value && value < bound;
+        (inside(moved.x, bounds.x) && inside(moved.y, bounds.y)).then_some(moved)
+    }
+
+    /// One step towards `dir` on a torus of `size`, leaving one edge comes back in from the
+    /// opposite one. Coordinates outside of `0..size` are wrapped into it first.
+    pub fn wrapping_moved(self, dir: impl Into<Dir8>, size: Self) -> Self {
+        let delta = dir.into().delta::<i8>();
+        Self::new(
+            wrapping_step(self.x, delta.x, size.x),
+            wrapping_step(self.y, delta.y, size.y),
+        )
+    }
+
+    pub fn checked_mul(self, factor: T) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_mul(factor)?,
+            self.y.checked_mul(factor)?,
+        ))
+    }
+
+    /// Both coordinates wrapped into `0..size`, negative ones included.
+    pub fn rem_euclid(self, size: Self) -> Self {
+        Self::new(self.x.rem_euclid(size.x), self.y.rem_euclid(size.y))
+    }
+}
+
+impl<T> Rem for Vec2D<T>
+where
+    T: Rem<Output = T>,
+    Vec2D<T>: RemAssign,
+{
+    type Output = Vec2D<T>;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        let mut new = self;
+        new.rem_assign(rhs);
+        new
+    }
+}
+
+impl<T> RemAssign for Vec2D<T>
+where
+    T: RemAssign,
+{
+    fn rem_assign(&mut self, rhs: Self) {
+        self.x %= rhs.x;
+        self.y %= rhs.y;
+    }
+}
+
+impl<T> Vec2D<T>
+where
+    T: TryInto<i128> + TryFrom<i128>,
+    <T as TryInto<i128>>::Error: Debug,
+    <T as TryFrom<i128>>::Error: Debug,
+{
+    pub fn manhattan_distance(self, other: Self) -> T {
+        let sx: i128 = self.x.try_into().unwrap();
+        let sy: i128 = self.y.try_into().unwrap();
+        let ox: i128 = other.x.try_into().unwrap();
+        let oy: i128 = other.y.try_into().unwrap();
+        let dist = (sx - ox).abs() + (sy - oy).abs();
+        dist.try_into().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions_keep_rows_and_columns() {
+        let pos = Pos2D::new(1, 5);
+        let vec = pos.to_vec();
+        assert_eq!(vec, Vec2D::new(5, 1));
+        assert_eq!(vec.to_pos(), pos);
+        assert_eq!(Vec2D::from(pos), vec);
+        assert_eq!(Pos2D::from(vec), pos);
+
+        for dir in [Dir::Up, Dir::Down, Dir::Left, Dir::Right] {
+            assert_eq!(pos.moved(dir).to_vec(), vec.moved(dir));
+        }
+    }
+
+    #[test]
+    fn points_and_offsets() {
+        let from = Pos2D::new(2, 3);
+        let to = Pos2D::new(4, 1);
+        let offset = from.make_vec_to(to);
+        assert_eq!(offset, Vec2D::new(-2, 2));
+        assert_eq!(from + offset, to);
+        assert_eq!(to - offset, from);
+        assert_eq!(to - from, offset);
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let corner = Pos2D::<usize>::new(0, 0);
+        assert_eq!(corner.checked_sub(Vec2D::new(0, 1)), None);
+        assert_eq!(corner.checked_add(Vec2D::new(2, 1)), Some(Pos2D::new(1, 2)));
+        assert_eq!(Vec2D::new(u8::MAX, 0).checked_add(Vec2D::new(1, 0)), None);
+        assert_eq!(
+            Vec2D::new(3_i64, -4).checked_mul(2),
+            Some(Vec2D::new(6, -8))
+        );
+        assert_eq!(
+            Vec2D::new(-1_i64, 12).rem_euclid(Vec2D::new(11, 7)),
+            Vec2D::new(10, 5)
+        );
+    }
+
+    #[test]
+    fn checked_moves_stop_at_the_edges() {
+        let corner = Pos2D::<usize>::new(0, 0);
+        assert_eq!(corner.checked_moved(Dir::Up), None);
+        assert_eq!(corner.checked_moved(Dir8::UpRight), None);
+        assert_eq!(
+            corner.checked_moved(Dir8::DownRight),
+            Some(Pos2D::new(1, 1))
+        );
+        assert_eq!(Vec2D::new(0_u8, 5).checked_moved(Dir8::UpLeft), None);
+        assert_eq!(
+            Vec2D::new(0_i32, 0).checked_moved(Dir8::UpLeft),
+            Some(Vec2D::new(-1, -1))
+        );
+        assert_eq!(Vec2D::new(3_u8, u8::MAX).checked_moved(Dir::Down), None);
+        assert_eq!(
+            Pos2D::new(2_i64, 3).checked_moved(Dir::Left),
+            Some(Pos2D::new(2, 3).left())
+        );
+    }
+
+    #[test]
+    fn bounded_and_wrapping_moves() {
+        let size = Vec2D::<usize>::new(4, 3);
+        let corner = Pos2D::<usize>::new(2, 3);
+        assert_eq!(corner.moved_within(Dir::Down, size), None);
+        assert_eq!(corner.moved_within(Dir::Up, size), Some(Pos2D::new(1, 3)));
+        assert_eq!(
+            corner.wrapping_moved(Dir8::DownRight, size),
+            Pos2D::new(0, 0)
+        );
+        assert_eq!(
+            Vec2D::new(0_i64, 0).moved_within(Dir::Left, Vec2D::new(4, 3)),
+            None
+        );
+        assert_eq!(
+            Vec2D::new(0_i64, 0).wrapping_moved(Dir8::UpLeft, Vec2D::new(4, 3)),
+            Vec2D::new(3, 2)
+        );
+    }
+
+    #[test]
+    fn signed_and_unsigned() {
+        let signed = Pos2D::new(-1_i64, 4);
+        assert_eq!(signed.try_cast::<usize>(), None);
+        assert_eq!(
+            Pos2D::new(3_i64, 4).try_cast::<usize>(),
+            Some(Pos2D::new(3, 4))
+        );
+        assert_eq!(
+            Vec2D::new(3_usize, 4).try_cast::<i64>(),
+            Some(Vec2D::new(3, 4))
+        );
+    }
+
+    mod props {
+        use super::*;
+        use proptest::prelude::*;
+        use proptest::sample::select;
+        use strum::IntoEnumIterator;
+
+        fn dir8() -> impl Strategy<Value = Dir8> {
+            select(Dir8::iter().collect::<Vec<_>>())
+        }
+
+        proptest! {
+            #[test]
+            fn checked_moves_match_signed_moves(x in any::<u8>(), y in any::<u8>(), dir in dir8()) {
+                let signed = Vec2D::new(i16::from(x), i16::from(y)) + dir.delta();
+                let moved = Vec2D::new(x, y).checked_moved(dir);
+                prop_assert_eq!(moved, signed.try_cast::<u8>());
+                prop_assert_eq!(
+                    Pos2D::new(y, x).checked_moved(dir),
+                    moved.map(Vec2D::to_pos)
+                );
+            }
+
+            #[test]
+            fn checked_moves_come_back(x in any::<i8>(), y in any::<i8>(), dir in dir8()) {
+                let start = Vec2D::new(x, y);
+                if let Some(moved) = start.checked_moved(dir) {
+                    prop_assert_eq!(moved.checked_moved(dir.opposite()), Some(start));
+                }
+            }
+
+            #[test]
+            fn moves_within_stay_inside(
+                (size, x, y) in (1_usize..50, 1_usize..50)
+                    .prop_flat_map(|(w, h)| (Just(Vec2D::new(w, h)), 0..w, 0..h)),
+                dir in dir8(),
+            ) {
+                let start = Vec2D::new(x, y);
+                let within = start.moved_within(dir, size);
+                let wrapped = start.wrapping_moved(dir, size);
+                prop_assert!(wrapped.x < size.x && wrapped.y < size.y);
+                match within {
+                    Some(moved) => {
+                        prop_assert!(moved.x < size.x && moved.y < size.y);
+                        prop_assert_eq!(moved, wrapped);
+                    }
+                    None => prop_assert_ne!(start.checked_moved(dir), Some(wrapped)),
+                }
+                prop_assert_eq!(wrapped.wrapping_moved(dir.opposite(), size), start);
+            }
+
+            #[test]
+            fn wrapping_matches_rem_euclid(
+                x in -100_i64..100,
+                y in -100_i64..100,
+                w in 1_i64..20,
+                h in 1_i64..20,
+                dir in dir8(),
+            ) {
+                let size = Vec2D::new(w, h);
+                let expected = (Vec2D::new(x, y) + dir.delta()).rem_euclid(size);
+                prop_assert_eq!(Vec2D::new(x, y).wrapping_moved(dir, size), expected);
+            }
+        }
+    }
+}
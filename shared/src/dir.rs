@@ -0,0 +1,185 @@
+use strum::EnumIter;
+
+use crate::Vec2D;
+
+#[derive(Debug, Hash, Eq, PartialEq, Copy, Clone, EnumIter)]
+pub enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Dir {
+    pub fn from_char(c: char) -> Option<Dir> {
+        match c {
+            '^' => Some(Dir::Up),
+            'v' => Some(Dir::Down),
+            '<' => Some(Dir::Left),
+            '>' => Some(Dir::Right),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Dir::Up => '^',
+            Dir::Down => 'v',
+            Dir::Left => '<',
+            Dir::Right => '>',
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Dir::Up => Dir::Down,
+            Dir::Down => Dir::Up,
+            Dir::Left => Dir::Right,
+            Dir::Right => Dir::Left,
+        }
+    }
+
+    pub fn rotated_90_cw(self) -> Self {
+        match self {
+            Dir::Up => Dir::Right,
+            Dir::Right => Dir::Down,
+            Dir::Down => Dir::Left,
+            Dir::Left => Dir::Up,
+        }
+    }
+
+    pub fn rotated_90_ccw(self) -> Self {
+        match self {
+            Dir::Up => Dir::Left,
+            Dir::Left => Dir::Down,
+            Dir::Down => Dir::Right,
+            Dir::Right => Dir::Up,
+        }
+    }
+
+    /// The offset of one step in this direction, see [`Dir8::delta`].
+    pub fn delta<T: From<i8>>(self) -> Vec2D<T> {
+        Dir8::from(self).delta()
+    }
+}
+
+/// The four directions of [`Dir`] and the four diagonals between them, clockwise from [`Dir8::Up`].
+#[derive(Debug, Hash, Eq, PartialEq, Copy, Clone, EnumIter)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    const CLOCKWISE: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    fn rotated_cw_by(self, eighths: usize) -> Self {
+        Self::CLOCKWISE[(self as usize + eighths) % 8]
+    }
+
+    pub fn rotated_45_cw(self) -> Self {
+        self.rotated_cw_by(1)
+    }
+
+    pub fn rotated_45_ccw(self) -> Self {
+        self.rotated_cw_by(7)
+    }
+
+    pub fn rotated_90_cw(self) -> Self {
+        self.rotated_cw_by(2)
+    }
+
+    pub fn rotated_90_ccw(self) -> Self {
+        self.rotated_cw_by(6)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotated_cw_by(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.to_dir().is_none()
+    }
+
+    /// The [`Dir`] this is, `None` for the diagonals.
+    pub fn to_dir(self) -> Option<Dir> {
+        match self {
+            Dir8::Up => Some(Dir::Up),
+            Dir8::Right => Some(Dir::Right),
+            Dir8::Down => Some(Dir::Down),
+            Dir8::Left => Some(Dir::Left),
+            _ => None,
+        }
+    }
+
+    /// The offset of one step in this direction, with `y` growing downwards like the rows do.
+    pub fn delta<T: From<i8>>(self) -> Vec2D<T> {
+        let (x, y) = match self {
+            Dir8::Up => (0, -1),
+            Dir8::UpRight => (1, -1),
+            Dir8::Right => (1, 0),
+            Dir8::DownRight => (1, 1),
+            Dir8::Down => (0, 1),
+            Dir8::DownLeft => (-1, 1),
+            Dir8::Left => (-1, 0),
+            Dir8::UpLeft => (-1, -1),
+        };
+        Vec2D::new(x.into(), y.into())
+    }
+}
+
+impl From<Dir> for Dir8 {
+    fn from(dir: Dir) -> Self {
+        match dir {
+            Dir::Up => Dir8::Up,
+            Dir::Right => Dir8::Right,
+            Dir::Down => Dir8::Down,
+            Dir::Left => Dir8::Left,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn rotations() {
+        assert_eq!(Dir8::UpLeft.rotated_45_cw(), Dir8::Up);
+        assert_eq!(Dir8::Up.rotated_45_ccw(), Dir8::UpLeft);
+        assert_eq!(Dir8::DownRight.opposite(), Dir8::UpLeft);
+        for dir in Dir::iter() {
+            let dir8 = Dir8::from(dir);
+            assert_eq!(dir8.rotated_90_cw(), dir.rotated_90_cw().into());
+            assert_eq!(dir8.rotated_90_ccw(), dir.rotated_90_ccw().into());
+            assert_eq!(dir8.opposite().to_dir(), Some(dir.opposite()));
+        }
+    }
+
+    #[test]
+    fn deltas() {
+        assert_eq!(Dir8::UpRight.delta::<i64>(), Vec2D::new(1, -1));
+        assert_eq!(Dir::Left.delta::<i32>(), Vec2D::new(-1, 0));
+        for dir in Dir8::iter() {
+            let delta = dir.delta::<i32>();
+            assert_eq!(dir.opposite().delta(), Vec2D::new(-delta.x, -delta.y));
+            assert_eq!(dir.is_diagonal(), delta.x != 0 && delta.y != 0);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::fmt::{self, Display};
+use std::io;
+use std::str::FromStr;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Everything that can go wrong while loading and running a day.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// Malformed puzzle input, `line` and `column` are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// Malformed puzzle input that can't be pinned to a single location.
+    InvalidInput(String),
+    DayNotFound(String),
+    InvalidArgument(String),
+    /// A request to the Advent of Code website failed or was refused.
+    Remote(String),
+}
+
+impl Error {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Error::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Shifts the line of a parse error, for sections parsed separately from the rest of the input.
+    pub fn with_line_offset(self, offset: usize) -> Self {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => Error::Parse {
+                line: line + offset,
+                column,
+                message,
+            },
+            err => err,
+        }
+    }
+
+    /// Parse error pointing at `part`, which must be a subslice of the 1-based `line_no`-th `line`.
+    pub fn parse_at(line_no: usize, line: &str, part: &str, message: impl Into<String>) -> Self {
+        Error::parse(line_no, column_of(line, part), message)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{err}"),
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {line}, column {column}: {message}"),
+            Error::InvalidInput(message) => write!(f, "{message}"),
+            Error::DayNotFound(day) => write!(f, "Day not found `{day}`"),
+            Error::InvalidArgument(message) => write!(f, "{message}"),
+            Error::Remote(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+/// 1-based column at which `part` starts inside `line`, or 1 if `part` isn't a subslice of it.
+pub fn column_of(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    if offset
+        .checked_add(part.len())
+        .is_none_or(|end| end > line.len())
+    {
+        return 1;
+    }
+    match line.get(..offset) {
+        Some(prefix) => prefix.chars().count() + 1,
+        None => 1,
+    }
+}
+
+/// Parses `part` of the 1-based `line_no`-th `line`, reporting where it failed on error.
+pub fn parse_field<T>(line_no: usize, line: &str, part: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    part.trim().parse().map_err(|err| {
+        Error::parse_at(line_no, line, part, format!("cannot parse `{part}`: {err}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_of_subslices() {
+        let line = "p=0,4 v=3,-3";
+        let (pos, vel) = line.split_once(' ').unwrap();
+        assert_eq!(column_of(line, pos), 1);
+        assert_eq!(column_of(line, vel), 7);
+        assert_eq!(column_of(line, "v=3"), 1);
+    }
+
+    #[test]
+    fn field_errors_point_at_the_field() {
+        let line = "3   x4";
+        let part = line.split_whitespace().nth(1).unwrap();
+        let err = parse_field::<i32>(2, line, part).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Parse {
+                line: 2,
+                column: 5,
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 5: cannot parse `x4`: invalid digit found in string"
+        );
+        assert_eq!(parse_field::<i32>(1, "17", "17").unwrap(), 17);
+    }
+}
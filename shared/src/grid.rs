@@ -0,0 +1,314 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use strum::IntoEnumIterator;
+
+use crate::{parse_2d_map, Dir, Dir8, Error, Pos2D, Result, Vec2D};
+
+/// A coordinate that can index a [`Grid`]. A [`Pos2D`] is a `row` and a `col`, while a [`Vec2D`]
+/// is an `x` along the row and a `y` down the column, the way the grid-walking days use it.
+pub trait GridIndex: Copy + 'static {
+    fn from_row_col(row: usize, col: usize) -> Self;
+    fn row_col(self) -> (usize, usize);
+}
+
+impl GridIndex for Pos2D<usize> {
+    fn from_row_col(row: usize, col: usize) -> Self {
+        Pos2D::new(row, col)
+    }
+
+    fn row_col(self) -> (usize, usize) {
+        (self.row, self.col)
+    }
+}
+
+impl GridIndex for Vec2D<usize> {
+    fn from_row_col(row: usize, col: usize) -> Self {
+        Vec2D::new(col, row)
+    }
+
+    fn row_col(self) -> (usize, usize) {
+        (self.y, self.x)
+    }
+}
+
+/// A rectangular map stored row by row in a single `Vec`.
+/// Every lookup is bounds checked against both its width and its height.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Fails on rows of different lengths, reporting the first one that differs from the first row.
+    /// Empty rows at the end, like those of trailing blank lines, are dropped.
+    pub fn from_rows(mut rows: Vec<Vec<T>>) -> Result<Self> {
+        while rows.last().is_some_and(Vec::is_empty) {
+            rows.pop();
+        }
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        let mut cells = Vec::with_capacity(width * height);
+        for (idx, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(Error::parse(
+                    idx + 1,
+                    1,
+                    format!("expected a row of {width} cells, found {}", row.len()),
+                ));
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The width and height as the bounds of [`Vec2D::moved_within`] / [`Pos2D::moved_within`].
+    pub fn size(&self) -> Vec2D<usize> {
+        Vec2D::new(self.width, self.height)
+    }
+
+    pub fn contains<I: GridIndex>(&self, idx: I) -> bool {
+        let (row, col) = idx.row_col();
+        row < self.height && col < self.width
+    }
+
+    pub fn get<I: GridIndex>(&self, idx: I) -> Option<&T> {
+        self.contains(idx).then(|| &self.cells[self.offset(idx)])
+    }
+
+    pub fn get_mut<I: GridIndex>(&mut self, idx: I) -> Option<&mut T> {
+        if !self.contains(idx) {
+            return None;
+        }
+        let offset = self.offset(idx);
+        Some(&mut self.cells[offset])
+    }
+
+    /// One step from `idx` towards `dir`, diagonals included, or `None` when it leaves the grid.
+    pub fn moved<I: GridIndex>(&self, idx: I, dir: impl Into<Dir8>) -> Option<I> {
+        let delta = dir.into().delta::<isize>();
+        self.offset_by(idx, delta.y, delta.x)
+    }
+
+    /// The up to 4 orthogonal neighbours of `idx` inside the grid, in [`Dir`] order.
+    pub fn neighbours<I: GridIndex>(&self, idx: I) -> impl Iterator<Item = I> + '_ {
+        self.neighbours_dirs(idx).map(|(neighbour, _)| neighbour)
+    }
+
+    /// Like [`Grid::neighbours`], along with the direction each neighbour is in.
+    pub fn neighbours_dirs<I: GridIndex>(&self, idx: I) -> impl Iterator<Item = (I, Dir)> + '_ {
+        Dir::iter().filter_map(move |dir| Some((self.moved(idx, dir)?, dir)))
+    }
+
+    /// The up to 8 neighbours of `idx` inside the grid, diagonals included, clockwise from the top.
+    pub fn neighbours8<I: GridIndex>(&self, idx: I) -> impl Iterator<Item = I> + '_ {
+        Dir8::iter().filter_map(move |dir| self.moved(idx, dir))
+    }
+
+    /// Every coordinate of the grid, row by row.
+    pub fn positions<I: GridIndex>(&self) -> impl Iterator<Item = I> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| I::from_row_col(row, col)))
+    }
+
+    /// Every cell along with its coordinate, row by row.
+    pub fn iter<I: GridIndex>(&self) -> impl Iterator<Item = (I, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Every cell, row by row.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn find<I: GridIndex>(&self, value: &T) -> Option<I>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    pub fn find_all<'a, I: GridIndex>(&'a self, value: &'a T) -> impl Iterator<Item = I> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(idx, _)| idx)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {col} is outside of the grid");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn offset<I: GridIndex>(&self, idx: I) -> usize {
+        let (row, col) = idx.row_col();
+        row * self.width + col
+    }
+
+    fn offset_by<I: GridIndex>(&self, idx: I, rows: isize, cols: isize) -> Option<I> {
+        let (row, col) = idx.row_col();
+        let moved = I::from_row_col(row.checked_add_signed(rows)?, col.checked_add_signed(cols)?);
+        self.contains(moved).then_some(moved)
+    }
+}
+
+impl Grid<char> {
+    /// A grid of the characters of each line, see [`parse_2d_map`].
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::from_rows(parse_2d_map(input))
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
+    type Error = Error;
+
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self> {
+        Self::from_rows(rows)
+    }
+}
+
+impl<T, I: GridIndex> Index<I> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, idx: I) -> &T {
+        let (row, col) = idx.row_col();
+        self.get(idx).unwrap_or_else(|| {
+            panic!(
+                "({row}, {col}) is outside of the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T, I: GridIndex> IndexMut<I> for Grid<T> {
+    fn index_mut(&mut self, idx: I) -> &mut T {
+        let (row, col) = idx.row_col();
+        let (width, height) = (self.width, self.height);
+        self.get_mut(idx)
+            .unwrap_or_else(|| panic!("({row}, {col}) is outside of the {width}x{height} grid"))
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Pos = Pos2D<usize>;
+    type Vec2 = Vec2D<usize>;
+
+    #[test]
+    fn indexing_by_both_coordinates() {
+        let mut grid = Grid::parse("abc\ndef\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Pos::new(1, 0)], 'd');
+        assert_eq!(grid[Vec2::new(1, 0)], 'b');
+        assert_eq!(grid.get(Pos::new(0, 3)), None);
+        assert_eq!(grid.get(Vec2::new(0, 2)), None);
+
+        grid[Pos::new(1, 2)] = 'x';
+        assert_eq!(grid.row(1), ['d', 'e', 'x']);
+        assert_eq!(grid.column(2).collect::<String>(), "cx");
+        assert_eq!(grid.to_string(), "abc\ndex\n");
+    }
+
+    #[test]
+    fn ragged_rows_are_rejected() {
+        assert!(matches!(
+            Grid::parse("abc\nde\n"),
+            Err(Error::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            Grid::parse("abc\n\nabc\n"),
+            Err(Error::Parse { line: 2, .. })
+        ));
+        assert_eq!(Grid::parse("abc\n\n").unwrap().height(), 1);
+
+        let mut grid = Grid::new(2, 2, 0);
+        assert_eq!(grid.get_mut(Pos::new(usize::MAX, usize::MAX)), None);
+    }
+
+    #[test]
+    fn neighbours_stay_inside() {
+        let grid = Grid::new(3, 3, 0);
+        let corner: Vec<Pos> = grid.neighbours(Pos::new(0, 0)).collect();
+        assert_eq!(corner, [Pos::new(1, 0), Pos::new(0, 1)]);
+        assert_eq!(grid.neighbours(Pos::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Pos::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Vec2::new(2, 2)).count(), 3);
+        assert_eq!(
+            grid.moved(Pos::new(0, 1), Dir8::DownLeft),
+            Some(Pos::new(1, 0))
+        );
+        assert_eq!(grid.moved(Pos::new(0, 1), Dir8::UpLeft), None);
+        assert_eq!(grid.moved(Vec2::new(2, 0), Dir::Right), None);
+        assert_eq!(
+            grid.moved(Vec2::new(2, 0), Dir::Down),
+            Some(Vec2::new(2, 1))
+        );
+    }
+
+    #[test]
+    fn finding_cells() {
+        let grid = Grid::parse("#.#\n.S.\n").unwrap();
+        assert_eq!(grid.find(&'S'), Some(Pos::new(1, 1)));
+        assert_eq!(grid.find::<Pos>(&'E'), None);
+        let walls: Vec<Vec2> = grid.find_all(&'#').collect();
+        assert_eq!(walls, [Vec2::new(0, 0), Vec2::new(2, 0)]);
+    }
+}
@@ -0,0 +1,78 @@
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use crate::{Error, Result};
+
+/// Locates the data files of each day (its puzzle `input`, `params`, `answers`, ...),
+/// so nothing depends on the process' working directory.
+pub trait InputProvider: Sync {
+    fn path(&self, day: &str, name: &str) -> PathBuf;
+
+    /// Reads the `name` file of `day`, errors mention the full path.
+    fn read(&self, day: &str, name: &str) -> Result<String> {
+        let path = self.path(day, name);
+        fs::read_to_string(&path).map_err(|err| {
+            Error::Io(io::Error::new(
+                err.kind(),
+                format!("{}: {err}", path.display()),
+            ))
+        })
+    }
+
+    /// Like [`InputProvider::read`], but a missing file isn't an error.
+    fn read_optional(&self, day: &str, name: &str) -> Result<Option<String>> {
+        match self.read(day, name) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(Error::Io(err)) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Data files laid out as `<root>/<day>/<name>`, next to each day's crate.
+#[derive(Debug, Clone)]
+pub struct DataDir {
+    root: PathBuf,
+}
+
+impl DataDir {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        DataDir { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl InputProvider for DataDir {
+    fn path(&self, day: &str, name: &str) -> PathBuf {
+        self.root.join(day).join(name)
+    }
+}
+
+/// Absolute path of a data file of the calling day crate, e.g. `shared::day_file!("sample_input")`.
+#[macro_export]
+macro_rules! day_file {
+    ($name:literal) => {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/", $name)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_of_a_day() {
+        let data = DataDir::new(concat!(env!("CARGO_MANIFEST_DIR"), "/.."));
+        assert!(data.path("day01", "input").ends_with("day01/input"));
+        assert!(data
+            .read("day01", "sample_input")
+            .unwrap()
+            .starts_with("3   4"));
+        assert!(data.read_optional("day01", "missing").unwrap().is_none());
+        assert!(data.read("day01", "missing").is_err());
+        assert!(day_file!("Cargo.toml").starts_with('/'));
+    }
+}
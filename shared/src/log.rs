@@ -0,0 +1,165 @@
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::RwLock;
+
+/// How much the days report besides their answers, all of it on stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Nothing at all, not even progress bars.
+    Quiet,
+    /// Only progress bars of long running parts, when stderr is a terminal.
+    Normal,
+    /// Intermediate results, like the price of every region.
+    Verbose,
+    /// Step by step dumps of a solution's state, like maps or the registers of a machine.
+    Trace,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+type Sink = Box<dyn Fn(&str) + Send + Sync>;
+
+static SINK: RwLock<Option<Sink>> = RwLock::new(None);
+
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Trace,
+    }
+}
+
+pub fn enabled(verbosity: Verbosity) -> bool {
+    self::verbosity() >= verbosity
+}
+
+/// Sends the logged lines to `sink` instead of stderr, or back to stderr for `None`.
+/// Progress bars aren't drawn while a sink is set.
+pub fn set_sink(sink: Option<Sink>) {
+    *SINK.write().unwrap_or_else(|err| err.into_inner()) = sink;
+}
+
+fn has_sink() -> bool {
+    SINK.read().unwrap_or_else(|err| err.into_inner()).is_some()
+}
+
+#[doc(hidden)]
+pub fn write_line(args: fmt::Arguments) {
+    match &*SINK.read().unwrap_or_else(|err| err.into_inner()) {
+        Some(sink) => sink(&args.to_string()),
+        None => eprintln!("{args}"),
+    }
+}
+
+/// Logs a line when running with `-v` or more.
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Verbosity::Verbose) {
+            $crate::log::write_line(format_args!($($arg)*));
+        }
+    };
+}
+
+/// Logs a line when running with `-vv`.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Verbosity::Trace) {
+            $crate::log::write_line(format_args!($($arg)*));
+        }
+    };
+}
+
+/// A progress bar on stderr that can be advanced from several threads at once.
+/// It's only drawn when stderr is a terminal, no sink is set and the verbosity isn't [`Verbosity::Quiet`].
+pub struct Progress {
+    label: String,
+    len: u64,
+    done: AtomicU64,
+    drawn_percent: AtomicU64,
+    shown: bool,
+}
+
+impl Progress {
+    const WIDTH: u64 = 40;
+
+    pub fn new(label: &str, len: u64) -> Self {
+        let progress = Progress {
+            label: label.to_string(),
+            len,
+            done: AtomicU64::new(0),
+            drawn_percent: AtomicU64::new(0),
+            shown: enabled(Verbosity::Normal) && io::stderr().is_terminal() && !has_sink(),
+        };
+        if progress.shown {
+            progress.draw(0);
+        }
+        progress
+    }
+
+    pub fn inc(&self) {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        if !self.shown {
+            return;
+        }
+
+        // Redraw only when the percentage changes, not on every step
+        let percent = (done * 100).checked_div(self.len).unwrap_or(100);
+        if self.drawn_percent.fetch_max(percent, Ordering::Relaxed) < percent {
+            self.draw(done);
+        }
+    }
+
+    pub fn done(&self) -> u64 {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    fn draw(&self, done: u64) {
+        let filled = (done * Self::WIDTH)
+            .checked_div(self.len)
+            .unwrap_or(Self::WIDTH)
+            .min(Self::WIDTH);
+        let bar = "#".repeat(filled as usize) + &"-".repeat((Self::WIDTH - filled) as usize);
+        let mut stderr = io::stderr().lock();
+        let _ = write!(stderr, "\r{} [{bar}] {done}/{}", self.label, self.len);
+        let _ = stderr.flush();
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if self.shown {
+            // Clear the bar so it doesn't mix with the answers
+            eprint!("\r\x1b[2K");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbosity_levels_are_ordered() {
+        assert!(Verbosity::Trace > Verbosity::Verbose);
+        assert!(Verbosity::Normal > Verbosity::Quiet);
+    }
+
+    #[test]
+    fn progress_counts_from_threads() {
+        let progress = Progress::new("test", 100);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| (0..25).for_each(|_| progress.inc()));
+            }
+        });
+        assert_eq!(progress.done(), 100);
+    }
+}
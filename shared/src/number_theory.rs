@@ -0,0 +1,235 @@
+//! Exact integer algorithms for the puzzles that boil down to equations over the integers.
+//!
+//! Everything works on `i64` and does its intermediate arithmetic in `i128`, so products of
+//! coordinates and prize positions don't overflow. Results that don't fit back into an `i64`
+//! are reported as `None` like the ones that don't exist.
+
+use crate::{determinant, Vec2D};
+
+/// The greatest common divisor, never negative. `gcd(0, 0)` is `0`.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    extended_gcd(a, b).0
+}
+
+/// The least common multiple, never negative, or `None` when it doesn't fit into an `i64`.
+pub fn lcm(a: i64, b: i64) -> Option<i64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    let lcm = (i128::from(a) / i128::from(gcd(a, b)) * i128::from(b)).abs();
+    lcm.try_into().ok()
+}
+
+/// `(g, x, y)` where `g` is the [`gcd`] of `a` and `b`, and `a * x + b * y == g`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_wide(a.into(), b.into());
+    // Only `gcd(i64::MIN, i64::MIN)` and friends fall outside, so there's no sensible answer left
+    let narrow = |n: i128| i64::try_from(n).expect("extended gcd out of the range of i64");
+    (narrow(g), narrow(x), narrow(y))
+}
+
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` with `a * x ≡ 1 (mod m)` in `0..m`, `None` when `a` and `m` aren't coprime.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "modulus must be positive, got {m}");
+    let (g, x, _) = extended_gcd_wide(a.into(), m.into());
+    (g == 1).then(|| x.rem_euclid(m.into()) as i64)
+}
+
+/// The `x` in `0..m` satisfying every `x ≡ residue (mod modulus)` of `congruences`, along with
+/// `m`, the least common multiple of the moduli. The moduli needn't be coprime, `None` when
+/// the congruences contradict each other. No congruences at all are satisfied by `(0, 1)`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut res: i128 = 0;
+    let mut modulus: i128 = 1;
+    for &(residue, m) in congruences {
+        assert!(m > 0, "modulus must be positive, got {m}");
+        let m = i128::from(m);
+        // res + modulus * k ≡ residue (mod m)  <=>  modulus * k ≡ residue - res (mod m)
+        let (g, inv, _) = extended_gcd_wide(modulus, m);
+        let diff = i128::from(residue) - res;
+        if diff % g != 0 {
+            return None;
+        }
+        let step = m / g;
+        let k = (diff / g % step * inv).rem_euclid(step);
+        res += modulus * k;
+        modulus *= step;
+        res = res.rem_euclid(modulus);
+        i64::try_from(modulus).ok()?;
+    }
+    Some((res as i64, modulus as i64))
+}
+
+/// One solution `(x, y)` of `a * x + b * y == c`, `None` when there are none.
+pub fn solve_diophantine(a: i64, b: i64, c: i64) -> Option<Vec2D<i64>> {
+    let (_, sol) = particular_solution(a, b, c)?;
+    sol.try_cast()
+}
+
+/// Every solution `(x, y)` of `a * x + b * y == c` with both `x` and `y` at least `0`, by
+/// increasing `x`. `a` and `b` must be positive, otherwise there could be infinitely many.
+pub fn non_negative_solutions(a: i64, b: i64, c: i64) -> Vec<Vec2D<i64>> {
+    assert!(
+        a > 0 && b > 0,
+        "coefficients must be positive, got {a} and {b}"
+    );
+    let Some((g, sol)) = particular_solution(a, b, c) else {
+        return vec![];
+    };
+
+    // Every other solution is (x + k * step_x, y - k * step_y)
+    let (step_x, step_y) = (i128::from(b) / g, i128::from(a) / g);
+    let k_min = div_ceil(-sol.x, step_x);
+    let k_max = sol.y.div_euclid(step_y);
+    (k_min..=k_max)
+        .filter_map(|k| Vec2D::new(sol.x + k * step_x, sol.y - k * step_y).try_cast())
+        .collect()
+}
+
+/// The gcd of `a` and `b` and one solution of `a * x + b * y == c`, in `i128` so that scaling
+/// the Bézout coefficients up to `c` can't overflow.
+fn particular_solution(a: i64, b: i64, c: i64) -> Option<(i128, Vec2D<i128>)> {
+    let (g, x, y) = extended_gcd_wide(a.into(), b.into());
+    let c = i128::from(c);
+    if g == 0 {
+        return (c == 0).then_some((0, Vec2D::new(0, 0)));
+    }
+    if c % g != 0 {
+        return None;
+    }
+    let scale = c / g;
+    Some((g, Vec2D::new(x * scale, y * scale)))
+}
+
+fn div_ceil(n: i128, d: i128) -> i128 {
+    -(-n).div_euclid(d)
+}
+
+/// The only `Vec2D::new(a, b)` with `a * col1 + b * col2 == rhs`, by Cramer's rule. `None` when
+/// the columns are parallel, so there's no single solution, or when it isn't made of integers.
+pub fn cramer(col1: Vec2D<i64>, col2: Vec2D<i64>, rhs: Vec2D<i64>) -> Option<Vec2D<i64>> {
+    let wide = |vec: Vec2D<i64>| Vec2D::new(i128::from(vec.x), i128::from(vec.y));
+    let (col1, col2, rhs) = (wide(col1), wide(col2), wide(rhs));
+    let det = determinant(col1, col2);
+    if det == 0 {
+        return None;
+    }
+
+    let det_a = determinant(rhs, col2);
+    let det_b = determinant(col1, rhs);
+    if det_a % det != 0 || det_b % det != 0 {
+        return None;
+    }
+    Vec2D::new(det_a / det, det_b / det).try_cast()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn gcds() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(i64::MAX, i64::MAX - 1), None);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(4, 6), None);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn diophantine_equations() {
+        assert_eq!(non_negative_solutions(3, 5, 22), [Vec2D::new(4, 2)]);
+        assert_eq!(
+            non_negative_solutions(2, 4, 8),
+            [Vec2D::new(0, 2), Vec2D::new(2, 1), Vec2D::new(4, 0)]
+        );
+        assert!(non_negative_solutions(2, 4, 7).is_empty());
+        assert!(non_negative_solutions(3, 5, -1).is_empty());
+        assert_eq!(solve_diophantine(0, 0, 1), None);
+    }
+
+    #[test]
+    fn cramers_rule() {
+        let (a, b) = (Vec2D::new(94, 34), Vec2D::new(22, 67));
+        assert_eq!(
+            cramer(a, b, Vec2D::new(8400, 5400)),
+            Some(Vec2D::new(80, 40))
+        );
+        assert_eq!(cramer(a, b, Vec2D::new(8401, 5400)), None);
+        assert_eq!(cramer(a, a * 2, Vec2D::new(94, 34)), None);
+        // Far beyond what an `f64` represents exactly
+        let big = Vec2D::new(1 << 60, 1 << 60);
+        assert_eq!(
+            cramer(Vec2D::new(3, 1), Vec2D::new(1, 3), big + Vec2D::new(4, 4)),
+            Some(Vec2D::new((1 << 58) + 1, (1 << 58) + 1))
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn bezout_identity(a in -1_000_000_i64..1_000_000, b in -1_000_000_i64..1_000_000) {
+            let (g, x, y) = extended_gcd(a, b);
+            prop_assert!(g >= 0);
+            prop_assert_eq!(a * x + b * y, g);
+            if g != 0 {
+                prop_assert_eq!((a % g, b % g), (0, 0));
+            }
+        }
+
+        #[test]
+        fn every_non_negative_solution(a in 1_i64..40, b in 1_i64..40, c in 0_i64..400) {
+            let brute: Vec<_> = (0..=c / a)
+                .filter(|x| (c - a * x) % b == 0)
+                .map(|x| Vec2D::new(x, (c - a * x) / b))
+                .collect();
+            prop_assert_eq!(non_negative_solutions(a, b, c), brute);
+        }
+
+        #[test]
+        fn crt_solves_every_congruence(
+            congruences in prop::collection::vec((0_i64..100, 1_i64..12), 0..4)
+        ) {
+            if let Some((x, m)) = crt(&congruences) {
+                prop_assert!((0..m).contains(&x));
+                for (residue, modulus) in congruences {
+                    prop_assert_eq!(x.rem_euclid(modulus), residue.rem_euclid(modulus));
+                }
+            } else {
+                let brute = (0..12_i64.pow(4)).find(|x| {
+                    congruences
+                        .iter()
+                        .all(|&(r, m)| x.rem_euclid(m) == r.rem_euclid(m))
+                });
+                prop_assert_eq!(brute, None);
+            }
+        }
+    }
+}
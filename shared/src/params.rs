@@ -0,0 +1,157 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use crate::{Error, Result};
+
+/// Named puzzle constants of a day, such as a grid size, that differ between the
+/// examples and the real input. Days fall back to the real puzzle values for missing keys.
+/// The keys a day reads are remembered, so that ones it never asked for can be reported
+/// by [`Params::check_all_read`] instead of silently being ignored.
+#[derive(Debug, Default)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+    read: Mutex<BTreeSet<String>>,
+}
+
+impl Clone for Params {
+    fn clone(&self) -> Self {
+        Params {
+            values: self.values.clone(),
+            read: Mutex::new(self.read_keys().clone()),
+        }
+    }
+}
+
+impl PartialEq for Params {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, key: &str, value: impl ToString) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    pub fn set(&mut self, key: &str, value: impl ToString) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    /// Adds all values of `other`, overriding the ones already present.
+    pub fn extend(&mut self, other: Params) {
+        self.values.extend(other.values);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn get_or<T>(&self, key: &str, default: T) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.read_keys().insert(key.to_string());
+        match self.values.get(key) {
+            Some(value) => value.parse().map_err(|err| {
+                Error::InvalidArgument(format!(
+                    "Invalid value `{value}` for parameter `{key}`: {err}"
+                ))
+            }),
+            None => Ok(default),
+        }
+    }
+
+    /// Fails on the keys that were given but never read, like misspelled ones or ones
+    /// meant for another day.
+    pub fn check_all_read(&self) -> Result<()> {
+        let read = self.read_keys();
+        let unknown: Vec<_> = self
+            .values
+            .keys()
+            .filter(|key| !read.contains(*key))
+            .map(|key| format!("`{key}`"))
+            .collect();
+        if unknown.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidArgument(format!(
+                "Unknown parameter {}",
+                unknown.join(", ")
+            )))
+        }
+    }
+
+    fn read_keys(&self) -> std::sync::MutexGuard<'_, BTreeSet<String>> {
+        self.read.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Parses a single `key=value` assignment, as given on the command line.
+    pub fn parse_assignment(assignment: &str) -> Result<(String, String)> {
+        match assignment.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                Ok((key.trim().to_string(), value.trim().to_string()))
+            }
+            _ => Err(Error::InvalidArgument(format!(
+                "Invalid parameter `{assignment}`, expected `key=value`"
+            ))),
+        }
+    }
+
+    /// Parses a params file with one `key = value` per line, blank lines and `#` comments are skipped.
+    pub fn parse_file(contents: &str) -> Result<Params> {
+        let mut params = Params::new();
+        for (idx, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = Self::parse_assignment(line)
+                .map_err(|_| Error::parse(idx + 1, 1, "expected `key = value`"))?;
+            params.set(&key, value);
+        }
+        Ok(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_values_with_defaults() {
+        let params = Params::new().with("width", 7).with("name", "x");
+        assert_eq!(params.get_or("width", 71).unwrap(), 7);
+        assert_eq!(params.get_or("height", 71).unwrap(), 71);
+        assert!(params.get_or("name", 0).is_err());
+        assert!(params.check_all_read().is_ok());
+    }
+
+    #[test]
+    fn unread_keys_are_reported() {
+        let params = Params::new().with("width", 7).with("bytse", 12);
+        assert_eq!(params.get_or("width", 71).unwrap(), 7);
+        assert_eq!(params.get_or("bytes", 1024).unwrap(), 1024);
+        let err = params.check_all_read().unwrap_err();
+        assert_eq!(err.to_string(), "Unknown parameter `bytse`");
+    }
+
+    #[test]
+    fn file_and_overrides() {
+        let mut params = Params::parse_file("# sample\nwidth = 7\n\nheight=7\n").unwrap();
+        params.extend(Params::new().with("height", 11));
+        assert_eq!(params, Params::new().with("width", 7).with("height", 11));
+        assert!(matches!(
+            Params::parse_file("width = 7\nheight\n"),
+            Err(Error::Parse { line: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,268 @@
+//! Small building blocks for the shapes puzzle inputs keep coming in.
+//!
+//! [`lines`] and [`sections`] hand out [`Line`]s, which remember their 1-based number so that
+//! everything parsed out of them reports a [`Error::Parse`] pointing at the offending part.
+//! The parts passed to the [`Line`] methods must be subslices of its text for the columns to
+//! come out right, like with [`parse_field`].
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{parse_field, Error, Result};
+
+/// One line of the input along with its 1-based number.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub no: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(no: usize, text: &'a str) -> Self {
+        Line { no, text }
+    }
+
+    /// A parse error pointing at `part` of this line.
+    pub fn error(&self, part: &str, message: impl Into<String>) -> Error {
+        Error::parse_at(self.no, self.text, part, message)
+    }
+
+    /// `part` parsed as a `T`, surrounding whitespace ignored.
+    pub fn field<T>(&self, part: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        parse_field(self.no, self.text, part)
+    }
+
+    /// What follows `prefix` in `part`.
+    pub fn strip_prefix(&self, part: &'a str, prefix: &str) -> Result<&'a str> {
+        part.strip_prefix(prefix)
+            .ok_or_else(|| self.error(part, format!("expected `{prefix}`")))
+    }
+
+    /// `part` split around the first `sep`.
+    pub fn split_once(&self, part: &'a str, sep: &str) -> Result<(&'a str, &'a str)> {
+        part.split_once(sep)
+            .ok_or_else(|| self.error(part, format!("expected `<..>{sep}<..>`")))
+    }
+
+    /// `part` split around the first `sep` with both sides parsed as a `T`, like `47|53`.
+    pub fn pair<T>(&self, part: &'a str, sep: &str) -> Result<(T, T)>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let (lhs, rhs) = self.split_once(part, sep)?;
+        Ok((self.field(lhs)?, self.field(rhs)?))
+    }
+
+    /// Every `sep` separated item of `part` parsed as a `T`, like `75,47,61`.
+    pub fn list<T>(&self, part: &'a str, sep: &str) -> Result<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        part.split(sep).map(|item| self.field(item)).collect()
+    }
+
+    /// The whole line as `<key>: <value>`, both trimmed.
+    pub fn key_value(&self) -> Result<(&'a str, &'a str)> {
+        let (key, value) = self
+            .text
+            .split_once(':')
+            .ok_or_else(|| self.error(self.text, "expected `<key>: <value>`"))?;
+        Ok((key.trim(), value.trim()))
+    }
+
+    /// Every integer on the line, see [`int_parts`].
+    pub fn ints<T>(&self) -> Result<Vec<T>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        int_parts(self.text).map(|part| self.field(part)).collect()
+    }
+
+    /// Exactly `N` integers on the line, like the four of `p=0,4 v=3,-3`.
+    pub fn ints_n<T, const N: usize>(&self) -> Result<[T; N]>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let ints = self.ints()?;
+        let found = ints.len();
+        ints.try_into()
+            .map_err(|_| self.error(self.text, format!("expected {N} numbers, found {found}")))
+    }
+}
+
+/// Every line of `input`, numbered from 1.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, text)| Line::new(idx + 1, text))
+}
+
+/// Like [`lines`], without the blank ones.
+pub fn non_empty_lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    lines(input).filter(|line| !line.text.trim().is_empty())
+}
+
+/// Every non blank line of `input` parsed with `parse_line`, stopping at the first error.
+pub fn parse_lines<T>(input: &str, parse_line: impl FnMut(Line) -> Result<T>) -> Result<Vec<T>> {
+    non_empty_lines(input).map(parse_line).collect()
+}
+
+/// A block of consecutive non blank lines, see [`sections`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    lines: Vec<Line<'a>>,
+}
+
+impl<'a> Section<'a> {
+    /// The 1-based number of the first line of the section.
+    pub fn first_line(&self) -> usize {
+        self.lines[0].no
+    }
+
+    pub fn lines(&self) -> &[Line<'a>] {
+        &self.lines
+    }
+
+    /// The lines of the section joined back together.
+    pub fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| line.text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The whole section parsed as a `T`, with the lines of its errors counted from the start
+    /// of the input rather than from the start of the section.
+    pub fn parse<T>(&self) -> Result<T>
+    where
+        T: FromStr<Err = Error>,
+    {
+        self.text()
+            .parse()
+            .map_err(|err: Error| err.with_line_offset(self.first_line() - 1))
+    }
+
+    /// An error about the section as a whole, pointing at its first line.
+    pub fn error(&self, message: impl Into<String>) -> Error {
+        Error::parse(self.first_line(), 1, message)
+    }
+}
+
+/// The blocks of `input` separated by blank lines, any number of them in a row.
+pub fn sections(input: &str) -> impl Iterator<Item = Section<'_>> {
+    let mut lines = lines(input).peekable();
+    std::iter::from_fn(move || {
+        while lines.next_if(|line| line.text.trim().is_empty()).is_some() {}
+        let section: Vec<_> =
+            std::iter::from_fn(|| lines.next_if(|line| !line.text.trim().is_empty())).collect();
+        (!section.is_empty()).then_some(Section { lines: section })
+    })
+}
+
+/// The integers in `text` as subslices of it, a `+` or `-` right before the digits included.
+/// Anything else in between is skipped, so `p=0,4 v=3,-3` has `0`, `4`, `3` and `-3`.
+/// Digits running into letters, like `2a`, are kept whole so they fail to parse instead of
+/// quietly being cut short.
+pub fn int_parts(text: &str) -> impl Iterator<Item = &str> {
+    let bytes = text.as_bytes();
+    let mut idx = 0;
+    std::iter::from_fn(move || {
+        while idx < bytes.len() && !bytes[idx].is_ascii_digit() {
+            idx += 1;
+        }
+        if idx == bytes.len() {
+            return None;
+        }
+
+        let start = if idx > 0 && matches!(bytes[idx - 1], b'-' | b'+') {
+            idx - 1
+        } else {
+            idx
+        };
+        while idx < bytes.len() && (bytes[idx].is_ascii_alphanumeric() || bytes[idx] == b'_') {
+            idx += 1;
+        }
+        Some(&text[start..idx])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_anywhere() {
+        let line = Line::new(3, "p=0,4 v=3,-3");
+        assert_eq!(line.ints::<i64>().unwrap(), [0, 4, 3, -3]);
+        assert_eq!(line.ints_n::<i64, 4>().unwrap(), [0, 4, 3, -3]);
+        assert!(matches!(
+            line.ints_n::<i64, 2>(),
+            Err(Error::Parse { line: 3, .. })
+        ));
+        assert!(matches!(
+            Line::new(1, "a=1 b=-300").ints::<i8>(),
+            Err(Error::Parse { column: 7, .. })
+        ));
+        assert_eq!(
+            int_parts("Button A: X+94, Y-34").collect::<Vec<_>>(),
+            ["+94", "-34"]
+        );
+        assert!(matches!(
+            Line::new(1, "X+2a, Y+67").ints::<i64>(),
+            Err(Error::Parse { column: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn fields_point_at_their_column() {
+        let line = Line::new(2, "47|x3");
+        assert!(matches!(
+            line.pair::<u64>(line.text, "|"),
+            Err(Error::Parse {
+                line: 2,
+                column: 4,
+                ..
+            })
+        ));
+        let line = Line::new(1, "Register A: 729");
+        assert_eq!(line.key_value().unwrap(), ("Register A", "729"));
+        let line = Line::new(1, "Program: 0,1,5,4");
+        let (_, program) = line.key_value().unwrap();
+        assert_eq!(line.list::<u8>(program, ",").unwrap(), [0, 1, 5, 4]);
+        assert!(Line::new(1, "Program 0").key_value().is_err());
+    }
+
+    #[test]
+    fn blank_lines_separate_sections() {
+        let input = "a\nb\n\n\n c \n\nd\n\n";
+        let found: Vec<_> = sections(input).collect();
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].text(), "a\nb");
+        assert_eq!(found[1].first_line(), 5);
+        assert_eq!(found[2].lines(), [Line::new(7, "d")]);
+        assert_eq!(sections(" \n").count(), 0);
+
+        let err = found[2].parse::<Number>().err().unwrap();
+        assert!(matches!(err, Error::Parse { line: 7, .. }));
+    }
+
+    struct Number;
+
+    impl FromStr for Number {
+        type Err = Error;
+
+        fn from_str(s: &str) -> Result<Self> {
+            Line::new(1, s).field::<u8>(s).map(|_| Number)
+        }
+    }
+}
@@ -0,0 +1,290 @@
+//! Shortest path searches over any graph, given as a closure listing the neighbours of a state.
+//!
+//! [`bfs`] and [`dijkstra`] explore everything reachable and return [`Paths`], which keeps
+//! every predecessor on a shortest path so all of those paths can be rebuilt afterwards.
+//! [`astar`] stops at the first goal it reaches and returns only that path.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// The distances of every reached state and the predecessors they were reached from
+/// on a shortest path, which form a DAG leading back to the start states.
+#[derive(Debug, Clone)]
+pub struct Paths<S> {
+    dists: HashMap<S, usize>,
+    preds: HashMap<S, Vec<S>>,
+}
+
+impl<S: Clone + Eq + Hash> Paths<S> {
+    fn new(starts: impl IntoIterator<Item = S>) -> Self {
+        Paths {
+            dists: starts.into_iter().map(|start| (start, 0)).collect(),
+            preds: HashMap::new(),
+        }
+    }
+
+    /// Records reaching `next` from `from` with a total cost of `dist`,
+    /// returning whether it's a shorter way to it than any before.
+    fn relax(&mut self, from: &S, next: S, dist: usize) -> bool {
+        match self.dists.get(&next) {
+            Some(&known) if dist > known => false,
+            Some(&known) if dist == known => {
+                let preds = self.preds.entry(next).or_default();
+                if !preds.contains(from) {
+                    preds.push(from.clone());
+                }
+                false
+            }
+            _ => {
+                self.dists.insert(next.clone(), dist);
+                self.preds.insert(next, vec![from.clone()]);
+                true
+            }
+        }
+    }
+
+    pub fn dist(&self, state: &S) -> Option<usize> {
+        self.dists.get(state).copied()
+    }
+
+    pub fn dists(&self) -> &HashMap<S, usize> {
+        &self.dists
+    }
+
+    pub fn into_dists(self) -> HashMap<S, usize> {
+        self.dists
+    }
+
+    /// The states `state` is reached from on its shortest paths, none for a start state.
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.preds.get(state).map_or(&[], Vec::as_slice)
+    }
+
+    /// One shortest path from a start state to `target`, both included.
+    pub fn path_to(&self, target: &S) -> Option<Vec<S>> {
+        self.dists.get(target)?;
+        let mut path = vec![target.clone()];
+        while let Some(pred) = self.predecessors(path.last().unwrap()).first() {
+            path.push(pred.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every shortest path from a start state to `target`, none when it wasn't reached.
+    pub fn all_paths_to(&self, target: &S) -> Vec<Vec<S>> {
+        if !self.dists.contains_key(target) {
+            return vec![];
+        }
+
+        let preds = self.predecessors(target);
+        if preds.is_empty() {
+            return vec![vec![target.clone()]];
+        }
+
+        preds
+            .iter()
+            .flat_map(|pred| self.all_paths_to(pred))
+            .map(|mut path| {
+                path.push(target.clone());
+                path
+            })
+            .collect()
+    }
+
+    /// Every state lying on a shortest path to one of `targets`, the targets included.
+    pub fn on_shortest_paths(&self, targets: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<S> = targets
+            .into_iter()
+            .filter(|target| self.dists.contains_key(target))
+            .collect();
+        while let Some(state) = stack.pop() {
+            if seen.insert(state.clone()) {
+                stack.extend(self.predecessors(&state).iter().cloned());
+            }
+        }
+        seen
+    }
+}
+
+/// Breadth first search where every step costs 1.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+) -> Paths<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Paths::new(starts);
+    let mut queue: VecDeque<S> = paths.dists.keys().cloned().collect();
+
+    while let Some(state) = queue.pop_front() {
+        let dist = paths.dists[&state] + 1;
+        for next in neighbours(&state) {
+            if paths.relax(&state, next.clone(), dist) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    paths
+}
+
+/// Dijkstra's search, `neighbours` lists each neighbour along with the cost of stepping to it.
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+) -> Paths<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut paths = Paths::new(starts);
+    // The heap orders by cost alone, the states themselves are looked up by their index
+    let mut queued: Vec<S> = paths.dists.keys().cloned().collect();
+    let mut heap: BinaryHeap<_> = (0..queued.len()).map(|idx| Reverse((0, idx))).collect();
+
+    while let Some(Reverse((dist, idx))) = heap.pop() {
+        let state = queued[idx].clone();
+        if dist > paths.dists[&state] {
+            continue;
+        }
+
+        for (next, cost) in neighbours(&state) {
+            if paths.relax(&state, next.clone(), dist + cost) {
+                heap.push(Reverse((dist + cost, queued.len())));
+                queued.push(next);
+            }
+        }
+    }
+
+    paths
+}
+
+/// A* search from `start` to the first state `is_goal` accepts, returning the path there
+/// and its cost. `heuristic` must never overestimate the remaining cost to a goal.
+pub fn astar<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, usize)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut paths = Paths::new([start.clone()]);
+    let mut queued = vec![start.clone()];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+
+    while let Some(Reverse((_, dist, idx))) = heap.pop() {
+        let state = queued[idx].clone();
+        if dist > paths.dists[&state] {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some((paths.path_to(&state)?, dist));
+        }
+
+        for (next, cost) in neighbours(&state) {
+            if paths.relax(&state, next.clone(), dist + cost) {
+                heap.push(Reverse((
+                    dist + cost + heuristic(&next),
+                    dist + cost,
+                    queued.len(),
+                )));
+                queued.push(next);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3x3 grid of numbered cells, 4 is blocked.
+    ///   0 1 2
+    ///   3 # 5
+    ///   6 7 8
+    fn grid_neighbours(&cell: &usize) -> Vec<usize> {
+        let (row, col) = (cell / 3, cell % 3);
+        let mut res = vec![];
+        if row > 0 {
+            res.push(cell - 3);
+        }
+        if row < 2 {
+            res.push(cell + 3);
+        }
+        if col > 0 {
+            res.push(cell - 1);
+        }
+        if col < 2 {
+            res.push(cell + 1);
+        }
+        res.retain(|&cell| cell != 4);
+        res
+    }
+
+    #[test]
+    fn bfs_finds_all_shortest_paths() {
+        let paths = bfs([0], grid_neighbours);
+        assert_eq!(paths.dist(&8), Some(4));
+        assert_eq!(paths.dist(&4), None);
+        assert_eq!(paths.predecessors(&0), &[] as &[usize]);
+
+        let mut all = paths.all_paths_to(&8);
+        all.sort();
+        assert_eq!(all, [vec![0, 1, 2, 5, 8], vec![0, 3, 6, 7, 8]]);
+        assert_eq!(paths.path_to(&8).unwrap().len(), 5);
+        assert_eq!(paths.on_shortest_paths([5]), HashSet::from([0, 1, 2, 5]));
+        assert!(paths.all_paths_to(&4).is_empty());
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheap_steps() {
+        // Going down the left column costs 10, everything else 1
+        let paths = dijkstra([0], |&cell| {
+            grid_neighbours(&cell).into_iter().map(move |next| {
+                let steep = cell % 3 == 0 && next == cell + 3;
+                (next, if steep { 10 } else { 1 })
+            })
+        });
+        assert_eq!(paths.dist(&8), Some(4));
+        assert_eq!(paths.path_to(&8).unwrap(), [0, 1, 2, 5, 8]);
+        assert_eq!(paths.dist(&6), Some(6));
+        assert_eq!(paths.predecessors(&6), [7]);
+    }
+
+    #[test]
+    fn astar_stops_at_the_goal() {
+        let manhattan = |&cell: &usize| (2 - cell / 3) + (2 - cell % 3);
+        let mut expanded = 0;
+        let (path, cost) = astar(
+            0,
+            |&cell| {
+                expanded += 1;
+                grid_neighbours(&cell).into_iter().map(|next| (next, 1))
+            },
+            manhattan,
+            |&cell| cell == 8,
+        )
+        .unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!((path[0], path[4]), (0, 8));
+        assert!(expanded < 8);
+
+        let unreachable = astar(
+            0,
+            |_| Vec::<(usize, usize)>::new(),
+            |_| 0,
+            |&cell| cell == 8,
+        );
+        assert!(unreachable.is_none());
+    }
+}
@@ -0,0 +1,97 @@
+use std::fs;
+use std::marker::PhantomData;
+use std::path::Path;
+
+use crate::{Params, Result};
+
+/// A single day's puzzle: parses the raw input once and answers both parts from it.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    /// Parses the input for days with tunable puzzle constants, the rest ignore `params`.
+    fn parse_with(input: &str, _params: &Params) -> Result<Self::Input> {
+        Self::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> impl Answer;
+
+    fn part2(input: &Self::Input) -> impl Answer;
+
+    fn parse_file<P: AsRef<Path>>(file_path: P) -> Result<Self::Input> {
+        Self::parse(&fs::read_to_string(file_path)?)
+    }
+}
+
+/// What a part returns: either the answer itself, or a [`Result`] of it for the parts that can
+/// find out only while solving that an input which parsed fine has no answer.
+pub trait Answer {
+    fn into_answer(self) -> Result<String>;
+}
+
+macro_rules! display_answers {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Answer for $ty {
+                fn into_answer(self) -> Result<String> {
+                    Ok(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+display_answers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String, &str);
+
+impl<T: Answer> Answer for Result<T> {
+    fn into_answer(self) -> Result<String> {
+        self?.into_answer()
+    }
+}
+
+/// Object safe counterpart of [`Solution`], used to keep all days in one registry
+/// that's shared between the runner's worker threads.
+pub trait DynSolution: Sync {
+    fn load(&self, input: &str, params: &Params) -> Result<Box<dyn ParsedInput>>;
+}
+
+/// An already parsed input of some day, ready to be solved, possibly both parts at once.
+pub trait ParsedInput: Send + Sync {
+    fn part1(&self) -> Result<String>;
+    fn part2(&self) -> Result<String>;
+}
+
+struct Parsed<S: Solution> {
+    input: S::Input,
+    solution: PhantomData<fn() -> S>,
+}
+
+impl<S> ParsedInput for Parsed<S>
+where
+    S: Solution,
+    S::Input: Send + Sync,
+{
+    fn part1(&self) -> Result<String> {
+        S::part1(&self.input).into_answer()
+    }
+
+    fn part2(&self) -> Result<String> {
+        S::part2(&self.input).into_answer()
+    }
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync + 'static,
+    S::Input: Send + Sync,
+{
+    fn load(&self, input: &str, params: &Params) -> Result<Box<dyn ParsedInput>> {
+        let input = S::parse_with(input, params)?;
+        params.check_all_read()?;
+        Ok(Box::new(Parsed::<S> {
+            input,
+            solution: PhantomData,
+        }))
+    }
+}
@@ -0,0 +1,89 @@
+/// Generates one `#[test]` per expected answer of a day's samples.
+///
+/// Samples are read from a file in the crate's directory or given inline, optionally
+/// with the [`Params`](crate::Params) they are parsed with (e.g. a smaller grid). Each test
+/// either runs `part1`/`part2` of the solution, or evaluates an expression with the parsed
+/// input bound to a name:
+///
+/// ```ignore
+/// shared::sample_tests! {
+///     Day18;
+///     file "sample_input" with (width = 7, height = 7, bytes = 12) {
+///         sample_part1: part1 => 22,
+///     }
+///     text "1\n2\n3\n2024\n" {
+///         sample_part2: part2 => 23,
+///     }
+/// }
+/// ```
+///
+/// Answers are compared through their `Display` output. Attributes such as `#[ignore]`
+/// can be put in front of a test's name.
+#[macro_export]
+macro_rules! sample_tests {
+    (@load $solution:ty, file $path:literal, $params:expr) => {
+        <$solution as $crate::Solution>::parse_with(
+            &std::fs::read_to_string($crate::day_file!($path))
+                .expect(concat!("Failed to read `", $path, "`")),
+            &$params,
+        )
+        .expect(concat!("Failed to parse `", $path, "`"))
+    };
+    (@load $solution:ty, text $text:literal, $params:expr) => {
+        <$solution as $crate::Solution>::parse_with($text, &$params)
+            .expect("Failed to parse the sample")
+    };
+
+    (@tests $solution:ty, $load:expr;) => {};
+    (@tests $solution:ty, $load:expr; $(#[$meta:meta])* $name:ident: part1 => $expected:expr $(, $($rest:tt)*)?) => {
+        #[test]
+        $(#[$meta])*
+        fn $name() {
+            let input = $load;
+            let answer = <$solution as $crate::Solution>::part1(&input);
+            let answer = $crate::Answer::into_answer(answer).expect("Failed to solve part 1");
+            assert_eq!(answer, $expected.to_string());
+        }
+
+        $crate::sample_tests!(@tests $solution, $load; $($($rest)*)?);
+    };
+    (@tests $solution:ty, $load:expr; $(#[$meta:meta])* $name:ident: part2 => $expected:expr $(, $($rest:tt)*)?) => {
+        #[test]
+        $(#[$meta])*
+        fn $name() {
+            let input = $load;
+            let answer = <$solution as $crate::Solution>::part2(&input);
+            let answer = $crate::Answer::into_answer(answer).expect("Failed to solve part 2");
+            assert_eq!(answer, $expected.to_string());
+        }
+
+        $crate::sample_tests!(@tests $solution, $load; $($($rest)*)?);
+    };
+    (@tests $solution:ty, $load:expr;
+        $(#[$meta:meta])* $name:ident: |$input:ident| $answer:expr => $expected:expr $(, $($rest:tt)*)?) => {
+        #[test]
+        $(#[$meta])*
+        fn $name() {
+            let loaded = $load;
+            let $input = &loaded;
+            assert_eq!($answer.to_string(), $expected.to_string());
+        }
+
+        $crate::sample_tests!(@tests $solution, $load; $($($rest)*)?);
+    };
+
+    ($solution:ty; $(
+        $kind:ident $source:literal $(with ($($key:ident = $value:expr),* $(,)?))? { $($tests:tt)* }
+    )*) => {
+        $(
+            $crate::sample_tests!(
+                @tests $solution,
+                $crate::sample_tests!(
+                    @load $solution, $kind $source,
+                    $crate::Params::new()$($(.with(stringify!($key), $value))*)?
+                );
+                $($tests)*
+            );
+        )*
+    };
+}
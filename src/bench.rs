@@ -0,0 +1,203 @@
+use serde::Serialize;
+use shared::{DynSolution, InputProvider, Params};
+use std::hint::black_box;
+use std::panic::{self, AssertUnwindSafe};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::register_days::DayMap;
+use crate::runner;
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct PhaseStats {
+    pub day: &'static str,
+    pub phase: Phase,
+    pub iterations: usize,
+    pub min_ns: u128,
+    pub median_ns: u128,
+    pub mean_ns: u128,
+}
+
+#[derive(Serialize)]
+struct BenchError<'a> {
+    day: &'static str,
+    error: &'a str,
+}
+
+/// Benchmarks the given days and prints min/median/mean of every phase,
+/// either as a table or as one JSON object per line.
+pub fn bench_days(
+    data: &dyn InputProvider,
+    days_map: &DayMap,
+    days: &[&'static str],
+    iterations: usize,
+    part: Option<u8>,
+    params: &Params,
+    json: bool,
+) -> ExitCode {
+    let mut all_stats = vec![];
+    let mut failed = false;
+
+    for &day in days {
+        let res = panic::catch_unwind(AssertUnwindSafe(|| {
+            let input = runner::read_input(data, day, None)?;
+            let params = runner::read_params(data, day, params)?;
+            bench_day(day, days_map[day], &input, &params, iterations, part)
+        }));
+
+        let error = match res {
+            Ok(Ok(stats)) if json => {
+                stats.iter().for_each(runner::print_json);
+                continue;
+            }
+            Ok(Ok(stats)) => {
+                all_stats.extend(stats);
+                continue;
+            }
+            Ok(Err(err)) => err.to_string(),
+            Err(panic) => format!("panicked: {}", runner::panic_message(&panic)),
+        };
+
+        failed = true;
+        if json {
+            runner::print_json(&BenchError { day, error: &error });
+        } else {
+            eprintln!("{day}: {error}");
+        }
+    }
+
+    if !json {
+        print_stats_table(&all_stats);
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn bench_day(
+    day: &'static str,
+    solution: &dyn DynSolution,
+    input: &str,
+    params: &Params,
+    iterations: usize,
+    part: Option<u8>,
+) -> shared::Result<Vec<PhaseStats>> {
+    let mut parse_times = Vec::with_capacity(iterations);
+    let mut parsed = None;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let loaded = solution.load(black_box(input), params)?;
+        parse_times.push(start.elapsed());
+        parsed = Some(loaded);
+    }
+    let parsed = parsed.expect("At least one iteration is required");
+
+    let time_part = |part: &dyn Fn() -> shared::Result<String>| -> shared::Result<Vec<Duration>> {
+        (0..iterations)
+            .map(|_| {
+                let start = Instant::now();
+                black_box(part()?);
+                Ok(start.elapsed())
+            })
+            .collect()
+    };
+
+    let mut stats = vec![calc_stats(day, Phase::Parse, parse_times)];
+    if part != Some(2) {
+        stats.push(calc_stats(
+            day,
+            Phase::Part1,
+            time_part(&|| parsed.part1())?,
+        ));
+    }
+    if part != Some(1) {
+        stats.push(calc_stats(
+            day,
+            Phase::Part2,
+            time_part(&|| parsed.part2())?,
+        ));
+    }
+
+    Ok(stats)
+}
+
+fn calc_stats(day: &'static str, phase: Phase, mut times: Vec<Duration>) -> PhaseStats {
+    times.sort();
+    let nanos: Vec<u128> = times.iter().map(Duration::as_nanos).collect();
+    let len = nanos.len();
+    let median_ns = if len.is_multiple_of(2) {
+        (nanos[len / 2 - 1] + nanos[len / 2]) / 2
+    } else {
+        nanos[len / 2]
+    };
+
+    PhaseStats {
+        day,
+        phase,
+        iterations: len,
+        min_ns: nanos[0],
+        median_ns,
+        mean_ns: nanos.iter().sum::<u128>() / len as u128,
+    }
+}
+
+fn print_stats_table(stats: &[PhaseStats]) {
+    let fmt_ns = |ns: u128| format!("{:.2?}", Duration::from_nanos(ns as u64));
+    let rows: Vec<[String; 6]> = stats
+        .iter()
+        .map(|stat| {
+            [
+                stat.day.to_string(),
+                format!("{:?}", stat.phase).to_lowercase(),
+                stat.iterations.to_string(),
+                fmt_ns(stat.min_ns),
+                fmt_ns(stat.median_ns),
+                fmt_ns(stat.mean_ns),
+            ]
+        })
+        .collect();
+
+    runner::print_table(["Day", "Phase", "Runs", "Min", "Median", "Mean"], &rows);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_of_odd_sample() {
+        let times = [30, 10, 20].map(Duration::from_nanos).to_vec();
+        let stats = calc_stats("day01", Phase::Parse, times);
+        assert_eq!(
+            stats,
+            PhaseStats {
+                day: "day01",
+                phase: Phase::Parse,
+                iterations: 3,
+                min_ns: 10,
+                median_ns: 20,
+                mean_ns: 20,
+            }
+        );
+    }
+
+    #[test]
+    fn stats_of_even_sample() {
+        let times = [40, 10, 20, 90].map(Duration::from_nanos).to_vec();
+        let stats = calc_stats("day01", Phase::Part2, times);
+        assert_eq!(stats.min_ns, 10);
+        assert_eq!(stats.median_ns, 30);
+        assert_eq!(stats.mean_ns, 40);
+    }
+}
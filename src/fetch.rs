@@ -0,0 +1,89 @@
+use shared::{DataDir, Error, InputProvider};
+use std::fs;
+use std::process::ExitCode;
+
+use crate::website::{self, UreqClient, Website};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// Downloads the puzzle input of each given day into `<day>/input`, skipping the ones already there.
+pub fn fetch_days(data: &DataDir, days: &[&'static str], force: bool) -> shared::Result<ExitCode> {
+    let client = UreqClient::new();
+    let mut website = Website::new(&client, website::session_token(data.root())?);
+    let mut failed = false;
+
+    for &day in days {
+        match fetch_input(&mut website, data, day, force) {
+            Ok(Fetched::Cached) => println!("{day}: already downloaded"),
+            Ok(Fetched::Downloaded) => println!("{day}: downloaded"),
+            Err(err) => {
+                eprintln!("{day}: {err}");
+                failed = true;
+            }
+        }
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+/// Puzzle inputs never change, so an existing `<day>/input` is only downloaded again when `force`d.
+pub fn fetch_input(
+    website: &mut Website,
+    data: &dyn InputProvider,
+    day: &str,
+    force: bool,
+) -> shared::Result<Fetched> {
+    let path = data.path(day, "input");
+    if path.exists() && !force {
+        return Ok(Fetched::Cached);
+    }
+
+    let input = website.get(&format!("day/{}/input", website::day_number(day)?))?;
+    if input.trim().is_empty() {
+        return Err(Error::Remote(format!("The input of {day} is empty")));
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, input)?;
+    Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::website::stand_in;
+    use std::time::Duration;
+
+    #[test]
+    fn downloads_once() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().to_path_buf();
+        let data = DataDir::new(&root);
+        let (base_url, server) = stand_in::serve(vec![(200, "3   4\n4   3\n")]);
+        let client = UreqClient::new();
+        let mut website = Website::new(&client, "abc".to_string())
+            .with_base_url(&base_url)
+            .with_min_interval(Duration::ZERO);
+
+        let fetched = fetch_input(&mut website, &data, "day01", false).unwrap();
+        assert_eq!(fetched, Fetched::Downloaded);
+        let fetched = fetch_input(&mut website, &data, "day01", false).unwrap();
+        assert_eq!(fetched, Fetched::Cached);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].head.starts_with("GET /2024/day/1/input "));
+        let input = fs::read_to_string(root.join("day01/input")).unwrap();
+        assert_eq!(input, "3   4\n4   3\n");
+    }
+}
@@ -1,33 +1,466 @@
-use clap::{Arg, Command};
-use std::{env, io};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use shared::log::{self, Verbosity};
+use shared::{DataDir, Error, Params};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
+mod bench;
+mod fetch;
 mod register_days;
+mod runner;
+mod scaffold;
+mod submit;
+mod tui;
+mod verify;
+mod watch;
+mod website;
 
-fn main() -> io::Result<()> {
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> shared::Result<ExitCode> {
     let matches = Command::new("Advent of Code")
         .author("Alexander Dimitrov")
         .about("Runs Advent of Code solutions")
+        .args_conflicts_with_subcommands(true)
+        .subcommand_negates_reqs(true)
         .arg(
             Arg::new("day")
-                .help("The day to run (e.g., day01)")
+                .help("The day to run (e.g., day01) or an inclusive range of days (e.g., day05..day12)")
                 .required(true)
                 .index(1),
         )
+        .arg(part_arg())
+        .arg(param_arg())
+        .arg(jobs_arg())
+        .arg(format_arg())
+        .arg(
+            Arg::new("verbose")
+                .help("Print what the solutions are doing to stderr, twice for step by step dumps")
+                .long("verbose")
+                .short('v')
+                .action(ArgAction::Count)
+                .global(true),
+        )
+        .arg(
+            Arg::new("quiet")
+                .help("Don't print anything besides the results, not even progress bars")
+                .long("quiet")
+                .short('q')
+                .action(ArgAction::SetTrue)
+                .conflicts_with("verbose")
+                .global(true),
+        )
+        .arg(
+            Arg::new("data_dir")
+                .help("Directory holding the `<day>/` data directories, by default the workspace \
+                       the current directory is in, or the current directory itself")
+                .long("data-dir")
+                .global(true),
+        )
+        .arg(
+            Arg::new("input")
+                .help("Input file to use instead of `<day>/input`, `-` reads from stdin")
+                .long("input")
+                .short('i'),
+        )
+        .subcommand(
+            Command::new("all")
+                .about("Runs every registered day and prints a summary")
+                .arg(part_arg())
+                .arg(param_arg())
+                .arg(jobs_arg())
+                .arg(format_arg()),
+        )
+        .subcommand(
+            Command::new("verify")
+                .about("Checks the answers of the given days against their `<day>/answers` files")
+                .arg(
+                    Arg::new("days")
+                        .help("The day or range of days to verify, all days by default")
+                        .index(1),
+                )
+                .arg(param_arg()),
+        )
+        .subcommand(
+            Command::new("bench")
+                .about("Benchmarks parsing and both parts of the given days")
+                .arg(
+                    Arg::new("days")
+                        .help("The day or range of days to benchmark, all days by default")
+                        .index(1),
+                )
+                .arg(part_arg())
+                .arg(
+                    Arg::new("iterations")
+                        .help("How many times to repeat each phase")
+                        .long("iterations")
+                        .short('n')
+                        .default_value("10")
+                        .value_parser(value_parser!(u32).range(1..)),
+                )
+                .arg(param_arg())
+                .arg(format_arg()),
+        )
+        .subcommand(
+            Command::new("fetch")
+                .about("Downloads the puzzle input of the given days into `<day>/input`")
+                .long_about(
+                    "Downloads the puzzle input of the given days into `<day>/input`, \
+                     skipping inputs that were already downloaded. The session token is read \
+                     from the `AOC_SESSION` environment variable or the `.aoc-session` file.",
+                )
+                .arg(
+                    Arg::new("days")
+                        .help("The day or range of days to download")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("force")
+                        .help("Download the input again even if it already exists")
+                        .long("force")
+                        .short('f')
+                        .action(ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(
+            Command::new("submit")
+                .about("Solves a part of a day and submits the answer")
+                .long_about(
+                    "Solves a part of a day and submits the answer. Every checked answer is \
+                     recorded in `<day>/submissions` and answers ruled out by earlier attempts \
+                     are never submitted again.",
+                )
+                .arg(
+                    Arg::new("day")
+                        .help("The day to submit (e.g., day07)")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("part")
+                        .help("The part to submit")
+                        .required(true)
+                        .index(2)
+                        .value_parser(value_parser!(u8).range(1..=2)),
+                )
+                .arg(param_arg()),
+        )
+        .subcommand(
+            Command::new("watch")
+                .about("Re-runs the sample tests and the puzzle input of a day whenever its files change")
+                .arg(
+                    Arg::new("day")
+                        .help("The day to watch (e.g., day07)")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("release")
+                        .help("Build in release mode, for days that are slow otherwise")
+                        .long("release")
+                        .action(ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(
+            Command::new("tui")
+                .about("Opens a terminal dashboard for browsing and running the days")
+                .arg(param_arg()),
+        )
+        .subcommand(
+            Command::new("new")
+                .about("Creates a new day crate from the template and adds it to the workspace")
+                .arg(
+                    Arg::new("day")
+                        .help("The day to create (e.g., day07 or 7)")
+                        .required(true)
+                        .index(1),
+                ),
+        )
         .get_matches();
 
-    let day = matches
+    log::set_verbosity(match matches.get_count("verbose") {
+        _ if matches.get_flag("quiet") => Verbosity::Quiet,
+        0 => Verbosity::Normal,
+        1 => Verbosity::Verbose,
+        _ => Verbosity::Trace,
+    });
+
+    let data = DataDir::new(match matches.get_one::<String>("data_dir") {
+        Some(dir) => PathBuf::from(dir),
+        None => default_data_dir()?,
+    });
+
+    if let Some(new_matches) = matches.subcommand_matches("new") {
+        let day = scaffold::day_name(new_matches.get_one::<String>("day").unwrap())?;
+        scaffold::create_day(data.root(), &day)?;
+        println!("Created {day}, run `fetch {day}` to download its puzzle input");
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let days_map = register_days::register_days();
+    let part = |matches: &ArgMatches| matches.get_one::<u8>("part").copied();
+    let json = |matches: &ArgMatches| matches.get_one::<String>("format").unwrap() == "json";
+    let jobs = |matches: &ArgMatches| *matches.get_one::<u32>("jobs").unwrap() as usize;
+    let params = |matches: &ArgMatches| -> shared::Result<Params> {
+        let mut params = Params::new();
+        for assignment in matches.get_many::<String>("param").into_iter().flatten() {
+            let (key, value) = Params::parse_assignment(assignment)?;
+            params.set(&key, value);
+        }
+        Ok(params)
+    };
+
+    if let Some(all_matches) = matches.subcommand_matches("all") {
+        let days = days_map.keys().copied().collect::<Vec<_>>();
+        return run_days(
+            &data,
+            &days_map,
+            &days,
+            part(all_matches),
+            &params(all_matches)?,
+            jobs(all_matches),
+            json(all_matches),
+        );
+    }
+
+    if let Some(watch_matches) = matches.subcommand_matches("watch") {
+        let day = watch_matches.get_one::<String>("day").unwrap();
+        let day = match *runner::select_days(&days_map, day)?.as_slice() {
+            [day] => day,
+            _ => {
+                return Err(Error::InvalidArgument(
+                    "Only a single day can be watched".to_string(),
+                ))
+            }
+        };
+        return watch::watch_day(&data, day, watch_matches.get_flag("release"));
+    }
+
+    if let Some(tui_matches) = matches.subcommand_matches("tui") {
+        tui::run_tui(&data, &days_map, &params(tui_matches)?)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(fetch_matches) = matches.subcommand_matches("fetch") {
+        let selector = fetch_matches.get_one::<String>("days").unwrap();
+        let days = runner::select_days(&days_map, selector)?;
+        let force = fetch_matches.get_flag("force");
+        return fetch::fetch_days(&data, &days, force);
+    }
+
+    if let Some(submit_matches) = matches.subcommand_matches("submit") {
+        let day = submit_matches.get_one::<String>("day").unwrap();
+        let day = match *runner::select_days(&days_map, day)?.as_slice() {
+            [day] => day,
+            _ => {
+                return Err(Error::InvalidArgument(
+                    "Only a single day can be submitted".to_string(),
+                ))
+            }
+        };
+        let part = *submit_matches.get_one::<u8>("part").unwrap();
+        let params = params(submit_matches)?;
+        return submit::submit_day(&data, &days_map, day, part, &params);
+    }
+
+    if let Some(verify_matches) = matches.subcommand_matches("verify") {
+        let days = match verify_matches.get_one::<String>("days") {
+            Some(selector) => runner::select_days(&days_map, selector)?,
+            None => days_map.keys().copied().collect(),
+        };
+        return Ok(verify::verify_days(
+            &data,
+            &days_map,
+            &days,
+            &params(verify_matches)?,
+        ));
+    }
+
+    if let Some(bench_matches) = matches.subcommand_matches("bench") {
+        let days = match bench_matches.get_one::<String>("days") {
+            Some(selector) => runner::select_days(&days_map, selector)?,
+            None => days_map.keys().copied().collect(),
+        };
+        let iterations = *bench_matches.get_one::<u32>("iterations").unwrap() as usize;
+        return Ok(bench::bench_days(
+            &data,
+            &days_map,
+            &days,
+            iterations,
+            part(bench_matches),
+            &params(bench_matches)?,
+            json(bench_matches),
+        ));
+    }
+
+    let selector = matches
         .get_one::<String>("day")
         .expect("Day argument is required");
+    let days = runner::select_days(&days_map, selector)?;
+    let input_path = matches.get_one::<String>("input").map(String::as_str);
+    let overrides = params(&matches)?;
+    let parallel_parts = jobs(&matches) > 1;
 
-    let days_map = register_days::register_days();
-    match days_map.get(day.as_str()) {
-        Some(func) => {
-            env::set_current_dir(day.as_str())?;
-            func()
+    match *days.as_slice() {
+        [day] if json(&matches) => {
+            let input = runner::read_input(&data, day, input_path);
+            let params = runner::read_params(&data, day, &overrides);
+            let solution = days_map[day];
+            let report = runner::run_day_with(
+                day,
+                solution,
+                input,
+                params,
+                part(&matches),
+                parallel_parts,
+                |_, _| (),
+            );
+            runner::print_json_report(&report, part(&matches));
+            Ok(exit_code(&[report]))
         }
-        None => Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!("Day not found `{day}`"),
+        [day] => {
+            let input = runner::read_input(&data, day, input_path)?;
+            let params = runner::read_params(&data, day, &overrides)?;
+            let solution = days_map[day];
+            let report = runner::run_day_with(
+                day,
+                solution,
+                Ok(input),
+                Ok(params),
+                part(&matches),
+                parallel_parts,
+                print_answer,
+            );
+            match report.error {
+                None => Ok(ExitCode::SUCCESS),
+                Some(err) => {
+                    eprintln!("error: {day}: {err}");
+                    Ok(ExitCode::FAILURE)
+                }
+            }
+        }
+        _ if input_path.is_some() => Err(Error::InvalidArgument(
+            "`--input` can only be used when running a single day".to_string(),
         )),
+        _ => run_days(
+            &data,
+            &days_map,
+            &days,
+            part(&matches),
+            &overrides,
+            jobs(&matches),
+            json(&matches),
+        ),
+    }
+}
+
+fn part_arg() -> Arg {
+    Arg::new("part")
+        .help("Run only the given part")
+        .long("part")
+        .short('p')
+        .value_parser(value_parser!(u8).range(1..=2))
+}
+
+fn param_arg() -> Arg {
+    Arg::new("param")
+        .help("Overrides a puzzle constant of the day, e.g. `width=7`, on top of `<day>/params`")
+        .long("param")
+        .short('P')
+        .value_name("KEY=VALUE")
+        .action(ArgAction::Append)
+}
+
+fn jobs_arg() -> Arg {
+    Arg::new("jobs")
+        .help("How many days and parts to run at once, results are still listed in calendar order")
+        .long("jobs")
+        .short('j')
+        .default_value("1")
+        .value_parser(value_parser!(u32).range(1..))
+}
+
+fn format_arg() -> Arg {
+    Arg::new("format")
+        .help("Output format, `json` prints one JSON object per line")
+        .long("format")
+        .default_value("text")
+        .value_parser(["text", "json"])
+}
+
+fn print_answer(part: u8, answer: &str) {
+    match part {
+        1 => println!("Part one result: {answer}"),
+        _ => println!("Part two result: {answer}"),
+    }
+}
+
+fn run_days(
+    data: &DataDir,
+    days_map: &register_days::DayMap,
+    days: &[&'static str],
+    part: Option<u8>,
+    params: &Params,
+    jobs: usize,
+    json: bool,
+) -> shared::Result<ExitCode> {
+    let reports = runner::solve_days(data, days_map, days, part, params, jobs)?;
+
+    if json {
+        reports
+            .iter()
+            .for_each(|report| runner::print_json_report(report, part));
+    } else {
+        runner::print_summary(&reports);
+    }
+
+    Ok(exit_code(&reports))
+}
+
+fn exit_code(reports: &[runner::DayReport]) -> ExitCode {
+    if reports.iter().all(|report| report.error.is_none()) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// The workspace root the current directory is in, or the current directory when it's in none.
+fn default_data_dir() -> shared::Result<PathBuf> {
+    let cwd = std::env::current_dir()?;
+    Ok(workspace_root(&cwd).unwrap_or(cwd))
+}
+
+/// The closest of `dir` and its ancestors with a `Cargo.toml` declaring a `[workspace]`.
+fn workspace_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|dir| {
+            std::fs::read_to_string(dir.join("Cargo.toml"))
+                .is_ok_and(|manifest| manifest.lines().any(|line| line.trim() == "[workspace]"))
+        })
+        .map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_root_from_a_member() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert_eq!(
+            workspace_root(&root.join("day01/src")),
+            Some(root.to_path_buf())
+        );
+        assert_eq!(workspace_root(root), Some(root.to_path_buf()));
     }
 }
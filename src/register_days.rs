@@ -1,61 +1,7 @@
-use std::collections::HashMap;
-use std::io;
+use shared::DynSolution;
+use std::collections::BTreeMap;
 
-type DayFunction = fn() -> io::Result<()>;
-type DayMap = HashMap<&'static str, DayFunction>;
+pub type DayMap = BTreeMap<&'static str, &'static dyn DynSolution>;
 
-pub fn register_days() -> DayMap {
-    let mut days: DayMap = HashMap::new();
-    use day07;
-    days.insert("day07", day07::run);
-    use day09;
-    days.insert("day09", day09::run);
-    use day08;
-    days.insert("day08", day08::run);
-    use day06;
-    days.insert("day06", day06::run);
-    use day01;
-    days.insert("day01", day01::run);
-    use day23;
-    days.insert("day23", day23::run);
-    use day24;
-    days.insert("day24", day24::run);
-    use day12;
-    days.insert("day12", day12::run);
-    use day15;
-    days.insert("day15", day15::run);
-    use day14;
-    days.insert("day14", day14::run);
-    use day13;
-    days.insert("day13", day13::run);
-    use day25;
-    days.insert("day25", day25::run);
-    use day22;
-    days.insert("day22", day22::run);
-    use day04;
-    days.insert("day04", day04::run);
-    use day03;
-    days.insert("day03", day03::run);
-    use day02;
-    days.insert("day02", day02::run);
-    use day05;
-    days.insert("day05", day05::run);
-    use day18;
-    days.insert("day18", day18::run);
-    use day20;
-    days.insert("day20", day20::run);
-    use day16;
-    days.insert("day16", day16::run);
-    use day11;
-    days.insert("day11", day11::run);
-    use day10;
-    days.insert("day10", day10::run);
-    use day17;
-    days.insert("day17", day17::run);
-    use day21;
-    days.insert("day21", day21::run);
-    use day19;
-    days.insert("day19", day19::run);
-
-    days
-}
+// Generated by `build.rs` from the `dayNN` directories, in calendar order.
+include!(concat!(env!("OUT_DIR"), "/register_days.rs"));
@@ -0,0 +1,388 @@
+use rayon::prelude::*;
+use serde::Serialize;
+use shared::{DynSolution, Error, InputProvider, Params};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+use std::{fs, io};
+
+use crate::register_days::DayMap;
+
+pub struct DayReport {
+    pub day: &'static str,
+    pub part1: Option<PartReport>,
+    pub part2: Option<PartReport>,
+    pub elapsed: Duration,
+    pub error: Option<String>,
+}
+
+pub struct PartReport {
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+/// A single line of the `--format json` output.
+#[derive(Serialize)]
+struct PartRecord<'a> {
+    day: &'static str,
+    part: u8,
+    answer: Option<&'a str>,
+    duration_ns: u128,
+    error: Option<&'a str>,
+}
+
+/// Resolves a day selector, either a single day (`day05`) or an inclusive range (`day05..day12`),
+/// to the matching registered days in calendar order.
+pub fn select_days(days: &DayMap, selector: &str) -> shared::Result<Vec<&'static str>> {
+    let find = |day: &str| {
+        days.get_key_value(day)
+            .map(|(&name, _)| name)
+            .ok_or_else(|| Error::DayNotFound(day.to_string()))
+    };
+
+    match selector.split_once("..") {
+        Some((from, to)) => {
+            let (from, to) = (find(from)?, find(to)?);
+            if from > to {
+                return Err(Error::InvalidArgument(format!(
+                    "Empty day range `{selector}`"
+                )));
+            }
+            Ok(days.range(from..=to).map(|(&name, _)| name).collect())
+        }
+        None => Ok(vec![find(selector)?]),
+    }
+}
+
+/// Reads the puzzle input of the day, or the file at `input_path` instead, `-` being stdin.
+pub fn read_input(
+    data: &dyn InputProvider,
+    day: &str,
+    input_path: Option<&str>,
+) -> shared::Result<String> {
+    match input_path {
+        Some("-") => Ok(io::read_to_string(io::stdin())?),
+        Some(path) => fs::read_to_string(path)
+            .map_err(|err| Error::Io(io::Error::new(err.kind(), format!("{path}: {err}")))),
+        None => match data.read_optional(day, "input")? {
+            Some(input) => Ok(input),
+            None => Err(Error::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "{} not found, run `fetch {day}` to download it",
+                    data.path(day, "input").display()
+                ),
+            ))),
+        },
+    }
+}
+
+/// Reads the day's `<day>/params` file if there is one, with `overrides` taking precedence.
+pub fn read_params(
+    data: &dyn InputProvider,
+    day: &str,
+    overrides: &Params,
+) -> shared::Result<Params> {
+    let mut params = match data.read_optional(day, "params")? {
+        Some(contents) => Params::parse_file(&contents).map_err(|err| {
+            let path = data.path(day, "params");
+            Error::InvalidInput(format!("{}: {err}", path.display()))
+        })?,
+        None => Params::new(),
+    };
+    params.extend(overrides.clone());
+    Ok(params)
+}
+
+/// Runs the requested parts of a day, reporting the answers as soon as they are known.
+/// Failures, including panics inside the solution, end up in the report instead of aborting.
+pub fn run_day(
+    day: &'static str,
+    solution: &dyn DynSolution,
+    input: shared::Result<String>,
+    params: shared::Result<Params>,
+    part: Option<u8>,
+    on_answer: impl FnMut(u8, &str),
+) -> DayReport {
+    run_day_with(day, solution, input, params, part, false, on_answer)
+}
+
+/// Runs the given days on up to `jobs` threads, both parts of a day at once when `jobs > 1`.
+/// The reports are in the same order as `days`.
+pub fn solve_days(
+    data: &dyn InputProvider,
+    days_map: &DayMap,
+    days: &[&'static str],
+    part: Option<u8>,
+    overrides: &Params,
+    jobs: usize,
+) -> shared::Result<Vec<DayReport>> {
+    let solve = |&day: &&'static str| {
+        let input = read_input(data, day, None);
+        let params = read_params(data, day, overrides);
+        run_day_with(day, days_map[day], input, params, part, jobs > 1, |_, _| ())
+    };
+
+    if jobs <= 1 {
+        return Ok(days.iter().map(solve).collect());
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .map_err(|err| Error::Io(io::Error::other(err)))?;
+    Ok(pool.install(|| days.par_iter().map(solve).collect()))
+}
+
+/// Like [`run_day`], running both parts at once when `parallel_parts` is set.
+pub fn run_day_with(
+    day: &'static str,
+    solution: &dyn DynSolution,
+    input: shared::Result<String>,
+    params: shared::Result<Params>,
+    part: Option<u8>,
+    parallel_parts: bool,
+    mut on_answer: impl FnMut(u8, &str),
+) -> DayReport {
+    let mut report = DayReport {
+        day,
+        part1: None,
+        part2: None,
+        elapsed: Duration::ZERO,
+        error: None,
+    };
+
+    let start = Instant::now();
+    let res = panic::catch_unwind(AssertUnwindSafe(|| -> shared::Result<()> {
+        let input = solution.load(&input?, &params?)?;
+
+        if parallel_parts && part.is_none() {
+            // The parts only share the parsed input, which they can't modify
+            let (part1, part2) =
+                rayon::join(|| run_part(|| input.part1()), || run_part(|| input.part2()));
+            let (part1, part2) = (part1?, part2?);
+            on_answer(1, &part1.answer);
+            on_answer(2, &part2.answer);
+            report.part1 = Some(part1);
+            report.part2 = Some(part2);
+            return Ok(());
+        }
+
+        if part != Some(2) {
+            let part1 = run_part(|| input.part1())?;
+            on_answer(1, &part1.answer);
+            report.part1 = Some(part1);
+        }
+
+        if part != Some(1) {
+            let part2 = run_part(|| input.part2())?;
+            on_answer(2, &part2.answer);
+            report.part2 = Some(part2);
+        }
+
+        Ok(())
+    }));
+    report.elapsed = start.elapsed();
+
+    report.error = match res {
+        Ok(Ok(())) => None,
+        Ok(Err(err)) => Some(err.to_string()),
+        Err(panic) => Some(format!("panicked: {}", panic_message(&panic))),
+    };
+
+    report
+}
+
+fn run_part(solve: impl FnOnce() -> shared::Result<String>) -> shared::Result<PartReport> {
+    let start = Instant::now();
+    let answer = solve()?;
+    Ok(PartReport {
+        answer,
+        elapsed: start.elapsed(),
+    })
+}
+
+pub fn print_summary(reports: &[DayReport]) {
+    let header = ["Day", "Part 1", "Part 2", "Time", "Status"];
+    let rows: Vec<[String; 5]> = reports
+        .iter()
+        .map(|report| {
+            [
+                report.day.to_string(),
+                report
+                    .part1
+                    .as_ref()
+                    .map_or("-", |res| &res.answer)
+                    .to_string(),
+                report
+                    .part2
+                    .as_ref()
+                    .map_or("-", |res| &res.answer)
+                    .to_string(),
+                format!("{:.2?}", report.elapsed),
+                match &report.error {
+                    None => "ok".to_string(),
+                    Some(err) => format!("error: {err}"),
+                },
+            ]
+        })
+        .collect();
+
+    print_table(header, &rows);
+}
+
+/// Prints one JSON object per requested part of the day. Parts that never produced
+/// an answer carry the day's error and the time spent until it occurred.
+pub fn print_json_report(report: &DayReport, part: Option<u8>) {
+    let parts = [(1, &report.part1), (2, &report.part2)];
+    for (part_num, res) in parts
+        .into_iter()
+        .filter(|&(num, _)| part.is_none_or(|p| p == num))
+    {
+        let record = match res {
+            Some(res) => PartRecord {
+                day: report.day,
+                part: part_num,
+                answer: Some(&res.answer),
+                duration_ns: res.elapsed.as_nanos(),
+                error: None,
+            },
+            None => PartRecord {
+                day: report.day,
+                part: part_num,
+                answer: None,
+                duration_ns: report.elapsed.as_nanos(),
+                error: Some(report.error.as_deref().unwrap_or("no answer")),
+            },
+        };
+        print_json(&record);
+    }
+}
+
+pub fn print_json(value: &impl Serialize) {
+    println!(
+        "{}",
+        serde_json::to_string(value).expect("Reports are always serializable")
+    );
+}
+
+/// Prints left aligned columns separated by two spaces, with a dashed line under the header.
+pub fn print_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) {
+    let mut widths = header.map(str::len);
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let print_row = |cells: &[&str]| {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    };
+
+    print_row(&header);
+    print_row(
+        &widths
+            .map(|width| "-".repeat(width))
+            .each_ref()
+            .map(String::as_str),
+    );
+    for row in rows {
+        print_row(&row.each_ref().map(String::as_str));
+    }
+}
+
+pub fn panic_message(panic: &Box<dyn Any + Send>) -> &str {
+    if let Some(msg) = panic.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = panic.downcast_ref::<String>() {
+        msg
+    } else {
+        "unknown panic"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::{Answer, DataDir, Solution};
+    use std::env;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+
+        fn parse(input: &str) -> shared::Result<Self::Input> {
+            input
+                .lines()
+                .enumerate()
+                .map(|(idx, line)| shared::parse_field(idx + 1, line, line))
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> impl Answer {
+            input.iter().sum::<u32>()
+        }
+
+        fn part2(input: &Self::Input) -> impl Answer {
+            if input.is_empty() {
+                return Err(Error::InvalidInput("nothing to multiply".to_string()));
+            }
+            Ok(input.iter().product::<u32>())
+        }
+    }
+
+    #[test]
+    fn parallel_parts_match_sequential() {
+        for parallel_parts in [false, true] {
+            let mut answers = vec![];
+            let input = Ok("2\n3\n4".to_string());
+            let report = run_day_with(
+                "day01",
+                &Sum,
+                input,
+                Ok(Params::new()),
+                None,
+                parallel_parts,
+                |part, answer| answers.push((part, answer.to_string())),
+            );
+            assert_eq!(answers, [(1, "9".to_string()), (2, "24".to_string())]);
+            assert_eq!(report.part2.unwrap().answer, "24");
+            assert!(report.error.is_none());
+        }
+    }
+
+    #[test]
+    fn part_errors_are_reported() {
+        let report = run_day(
+            "day01",
+            &Sum,
+            Ok(String::new()),
+            Ok(Params::new()),
+            None,
+            |_, _| (),
+        );
+        assert_eq!(report.part1.unwrap().answer, "0");
+        assert!(report.part2.is_none());
+        assert_eq!(report.error.as_deref(), Some("nothing to multiply"));
+    }
+
+    #[test]
+    fn solved_days_keep_their_order() {
+        let mut days_map = DayMap::new();
+        days_map.insert("day98", &Sum);
+        days_map.insert("day99", &Sum);
+        let data = DataDir::new(env::temp_dir().join("aoc-no-data"));
+        let days = ["day99", "day98"];
+        let reports = solve_days(&data, &days_map, &days, None, &Params::new(), 2).unwrap();
+        let days: Vec<_> = reports.iter().map(|report| report.day).collect();
+        assert_eq!(days, ["day99", "day98"]);
+        assert!(reports.iter().all(|report| report.error.is_some()));
+    }
+}
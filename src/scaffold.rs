@@ -0,0 +1,164 @@
+use shared::Error;
+use std::fs;
+use std::path::Path;
+
+const CARGO_TEMPLATE: &str = include_str!("templates/Cargo.toml.in");
+const LIB_TEMPLATE: &str = include_str!("templates/lib.rs.in");
+
+/// Normalizes `7`, `07` or `day07` to `day07`, accepting only days 1 to 25.
+pub fn day_name(arg: &str) -> shared::Result<String> {
+    let num = arg.strip_prefix("day").unwrap_or(arg);
+    match num.parse::<u8>() {
+        Ok(day @ 1..=25) if num.len() <= 2 => Ok(format!("day{day:02}")),
+        _ => Err(Error::InvalidArgument(format!(
+            "Invalid day `{arg}`, expected a day between 1 and 25"
+        ))),
+    }
+}
+
+/// Creates the `<day>` crate under `root` with a solution skeleton, an empty
+/// `sample_input` and its tests, and adds it to the workspace `Cargo.toml`.
+pub fn create_day(root: &Path, day: &str) -> shared::Result<()> {
+    let day_dir = root.join(day);
+    if day_dir.exists() {
+        return Err(Error::InvalidArgument(format!(
+            "`{}` already exists",
+            day_dir.display()
+        )));
+    }
+
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path)?;
+    let manifest = add_to_manifest(&manifest, day)
+        .map_err(|err| Error::InvalidInput(format!("{}: {err}", manifest_path.display())))?;
+
+    let solution = format!("D{}", &day[1..]);
+    let render = |template: &str| {
+        template
+            .replace("{{day}}", day)
+            .replace("{{Day}}", &solution)
+    };
+
+    fs::create_dir_all(day_dir.join("src"))?;
+    fs::write(day_dir.join("Cargo.toml"), render(CARGO_TEMPLATE))?;
+    fs::write(day_dir.join("src").join("lib.rs"), render(LIB_TEMPLATE))?;
+    fs::write(day_dir.join("sample_input"), "")?;
+    fs::write(manifest_path, manifest)?;
+    Ok(())
+}
+
+/// Adds the day as a dependency and a workspace member, keeping both lists in calendar order.
+fn add_to_manifest(manifest: &str, day: &str) -> shared::Result<String> {
+    let mut lines: Vec<String> = manifest.lines().map(str::to_string).collect();
+
+    let dependency = format!("{day} = {{ path = \"{day}\" }}");
+    insert_sorted(&mut lines, "[dependencies]", &dependency, |line| {
+        line.starts_with("day")
+    })?;
+
+    let member = format!("    \"{day}\",");
+    insert_sorted(&mut lines, "members = [", &member, |line| {
+        line.trim_start().starts_with("\"day")
+    })?;
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Inserts `new_line` among the consecutive `is_entry` lines following `section`,
+/// right before the first entry that sorts after it.
+fn insert_sorted(
+    lines: &mut Vec<String>,
+    section: &str,
+    new_line: &str,
+    is_entry: impl Fn(&str) -> bool,
+) -> shared::Result<()> {
+    let start = lines
+        .iter()
+        .position(|line| line.trim_end() == section)
+        .ok_or_else(|| Error::InvalidInput(format!("missing `{section}` section")))?;
+
+    let mut idx = start + 1;
+    while idx < lines.len() && !lines[idx].starts_with('[') && !lines[idx].starts_with(']') {
+        if is_entry(&lines[idx]) && lines[idx].as_str() > new_line {
+            break;
+        }
+        idx += 1;
+    }
+
+    // Keep new entries next to the existing ones rather than after trailing blank lines
+    while idx > start + 1 && lines[idx - 1].trim().is_empty() {
+        idx -= 1;
+    }
+
+    lines.insert(idx, new_line.to_string());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"[package]
+name = "aoc-2024"
+
+[dependencies]
+clap = "4"
+day01 = { path = "day01" }
+day03 = { path = "day03" }
+
+[workspace]
+members = [
+    "day01",
+    "day03",
+]
+"#;
+
+    #[test]
+    fn day_names() {
+        assert_eq!(day_name("7").unwrap(), "day07");
+        assert_eq!(day_name("day07").unwrap(), "day07");
+        assert_eq!(day_name("25").unwrap(), "day25");
+        assert!(day_name("0").is_err());
+        assert!(day_name("26").is_err());
+        assert!(day_name("day7x").is_err());
+        assert!(day_name("007").is_err());
+    }
+
+    #[test]
+    fn manifest_in_calendar_order() {
+        let manifest = add_to_manifest(MANIFEST, "day02").unwrap();
+        assert_eq!(
+            manifest,
+            MANIFEST
+                .replace(
+                    "day03 = { path",
+                    "day02 = { path = \"day02\" }\nday03 = { path"
+                )
+                .replace("    \"day03\",", "    \"day02\",\n    \"day03\",")
+        );
+    }
+
+    #[test]
+    fn manifest_appends_last_day() {
+        let manifest = add_to_manifest(MANIFEST, "day04").unwrap();
+        assert!(manifest.contains("day03 = { path = \"day03\" }\nday04 = { path = \"day04\" }\n\n"));
+        assert!(manifest.contains("    \"day03\",\n    \"day04\",\n]"));
+    }
+
+    #[test]
+    fn create_day_crate() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().to_path_buf();
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("Cargo.toml"), MANIFEST).unwrap();
+
+        create_day(&root, "day02").unwrap();
+        let lib = fs::read_to_string(root.join("day02/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day02;"));
+        assert!(lib.contains("Day02::parse_file(shared::day_file!(\"sample_input\"))"));
+        let cargo = fs::read_to_string(root.join("day02/Cargo.toml")).unwrap();
+        assert!(cargo.contains("name = \"day02\""));
+        assert!(root.join("day02/sample_input").exists());
+        assert!(create_day(&root, "day02").is_err());
+    }
+}
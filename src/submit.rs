@@ -0,0 +1,325 @@
+use shared::{DataDir, Error, InputProvider, Params};
+use std::fmt::{self, Display};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+use crate::register_days::DayMap;
+use crate::runner;
+use crate::website::{self, UreqClient, Website};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl Verdict {
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wrong => "wrong",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            Verdict::Correct,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Wrong,
+        ]
+        .into_iter()
+        .find(|verdict| verdict.as_str() == name)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wrong => "wrong",
+        })
+    }
+}
+
+/// What the website made of a submitted answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Reply {
+    Verdict(Verdict),
+    /// Answers were submitted too recently, holds how long is left to wait if the website said.
+    Wait(Option<String>),
+    /// The part was already solved, so the answer wasn't checked.
+    AlreadySolved,
+}
+
+fn parse_reply(html: &str) -> shared::Result<Reply> {
+    if html.contains("That's the right answer") {
+        Ok(Reply::Verdict(Verdict::Correct))
+    } else if html.contains("That's not the right answer") {
+        let verdict = if html.contains("too high") {
+            Verdict::TooHigh
+        } else if html.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        };
+        Ok(Reply::Verdict(verdict))
+    } else if html.contains("You gave an answer too recently") {
+        let left = html
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map(|(left, _)| left.to_string());
+        Ok(Reply::Wait(left))
+    } else if html.contains("Did you already complete it") {
+        Ok(Reply::AlreadySolved)
+    } else {
+        Err(Error::Remote(
+            "Unrecognized reply to the submitted answer".to_string(),
+        ))
+    }
+}
+
+/// A checked answer, one per line of `<day>/submissions` as `part1 too-low: 1234`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub part: u8,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+fn load_history(path: &Path) -> shared::Result<Vec<Attempt>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => parse_history(&contents),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn parse_history(contents: &str) -> shared::Result<Vec<Attempt>> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            let invalid = || Error::parse(idx + 1, 1, "expected `part<N> <verdict>: <answer>`");
+            let (key, answer) = line.split_once(':').ok_or_else(invalid)?;
+            let (part, verdict) = key.trim().split_once(' ').ok_or_else(invalid)?;
+            let part = match part {
+                "part1" => 1,
+                "part2" => 2,
+                _ => return Err(invalid()),
+            };
+            let verdict = Verdict::from_name(verdict.trim()).ok_or_else(invalid)?;
+            Ok(Attempt {
+                part,
+                verdict,
+                answer: answer.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+fn append_history(path: &Path, attempt: &Attempt) -> shared::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(
+        file,
+        "part{} {}: {}",
+        attempt.part,
+        attempt.verdict.as_str(),
+        attempt.answer
+    )?;
+    Ok(())
+}
+
+/// Refuses answers that earlier attempts already ruled out, including numbers past a known bound.
+fn check_history(history: &[Attempt], part: u8, answer: &str) -> shared::Result<()> {
+    let number = answer.parse::<i128>().ok();
+    for attempt in history.iter().filter(|attempt| attempt.part == part) {
+        let bound = attempt.answer.parse::<i128>().ok();
+        let ruled_out = match (attempt.verdict, number, bound) {
+            (Verdict::Correct, _, _) => {
+                return Err(Error::InvalidArgument(format!(
+                    "Part {part} was already solved with `{}`",
+                    attempt.answer
+                )))
+            }
+            _ if attempt.answer == answer => true,
+            (Verdict::TooHigh, Some(number), Some(bound)) => number >= bound,
+            (Verdict::TooLow, Some(number), Some(bound)) => number <= bound,
+            _ => false,
+        };
+
+        if ruled_out {
+            return Err(Error::InvalidArgument(format!(
+                "Not submitting `{answer}`, `{}` was already {} for part {part}",
+                attempt.answer, attempt.verdict
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Submits `answer` unless the history of `<day>/submissions` rules it out, and records the verdict.
+pub fn submit_answer(
+    website: &mut Website,
+    data: &dyn InputProvider,
+    day: &str,
+    part: u8,
+    answer: &str,
+) -> shared::Result<Reply> {
+    let history_path = data.path(day, "submissions");
+    check_history(&load_history(&history_path)?, part, answer)?;
+
+    let path = format!("day/{}/answer", website::day_number(day)?);
+    let level = part.to_string();
+    let html = website.post(&path, &[("level", &level), ("answer", answer)])?;
+    let reply = parse_reply(&html)?;
+
+    if let Reply::Verdict(verdict) = reply {
+        let attempt = Attempt {
+            part,
+            verdict,
+            answer: answer.to_string(),
+        };
+        append_history(&history_path, &attempt)?;
+    }
+
+    Ok(reply)
+}
+
+/// Solves the part of the day and submits the answer.
+pub fn submit_day(
+    data: &DataDir,
+    days_map: &DayMap,
+    day: &'static str,
+    part: u8,
+    params: &Params,
+) -> shared::Result<ExitCode> {
+    let input = runner::read_input(data, day, None);
+    let params = runner::read_params(data, day, params);
+    let report = runner::run_day(day, days_map[day], input, params, Some(part), |_, _| ());
+    if let Some(err) = report.error {
+        return Err(Error::InvalidInput(format!("{day}: {err}")));
+    }
+
+    let answer = match part {
+        1 => report.part1,
+        _ => report.part2,
+    }
+    .expect("The requested part was run")
+    .answer;
+
+    let client = UreqClient::new();
+    let mut website = Website::new(&client, website::session_token(data.root())?);
+    println!("Submitting `{answer}` for {day} part {part}");
+
+    match submit_answer(&mut website, data, day, part, &answer)? {
+        Reply::Verdict(Verdict::Correct) => {
+            println!("That's the right answer!");
+            return Ok(ExitCode::SUCCESS);
+        }
+        Reply::Verdict(verdict) => println!("That's not the right answer, it's {verdict}"),
+        Reply::Wait(Some(left)) => println!("Answered too recently, wait {left} and try again"),
+        Reply::Wait(None) => println!("Answered too recently, wait a bit and try again"),
+        Reply::AlreadySolved => println!("This part was already solved"),
+    }
+    Ok(ExitCode::FAILURE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::website::stand_in;
+    use std::time::Duration;
+
+    fn attempt(part: u8, verdict: Verdict, answer: &str) -> Attempt {
+        Attempt {
+            part,
+            verdict,
+            answer: answer.to_string(),
+        }
+    }
+
+    #[test]
+    fn replies() {
+        let reply = |html| parse_reply(html).unwrap();
+        assert_eq!(
+            reply("<p>That's the right answer!  You are one gold star closer.</p>"),
+            Reply::Verdict(Verdict::Correct)
+        );
+        assert_eq!(
+            reply("<p>That's not the right answer; your answer is too low.</p>"),
+            Reply::Verdict(Verdict::TooLow)
+        );
+        assert_eq!(
+            reply("<p>That's not the right answer.  If you're stuck, ...</p>"),
+            Reply::Verdict(Verdict::Wrong)
+        );
+        assert_eq!(
+            reply("<p>You gave an answer too recently; ...  You have 42s left to wait.</p>"),
+            Reply::Wait(Some("42s".to_string()))
+        );
+        assert_eq!(
+            reply("<p>You don't seem to be solving the right level.  Did you already complete it?"),
+            Reply::AlreadySolved
+        );
+        assert!(parse_reply("<p>Something else</p>").is_err());
+    }
+
+    #[test]
+    fn history_rules_out_answers() {
+        let history =
+            parse_history("part1 too-low: 100\npart1 too-high: 200\npart2 wrong: abc\n").unwrap();
+        assert_eq!(history[1], attempt(1, Verdict::TooHigh, "200"));
+        assert!(check_history(&history, 1, "150").is_ok());
+        assert!(check_history(&history, 1, "100").is_err());
+        assert!(check_history(&history, 1, "99").is_err());
+        assert!(check_history(&history, 1, "201").is_err());
+        assert!(check_history(&history, 2, "abc").is_err());
+        assert!(check_history(&history, 2, "150").is_ok());
+
+        let solved = [attempt(2, Verdict::Correct, "7")];
+        assert!(check_history(&solved, 2, "8").is_err());
+        assert!(parse_history("part3 wrong: 1").is_err());
+    }
+
+    #[test]
+    fn submits_and_records_attempts() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().to_path_buf();
+        fs::create_dir_all(root.join("day01")).unwrap();
+        let data = DataDir::new(&root);
+        let (base_url, server) = stand_in::serve(vec![
+            (
+                200,
+                "<p>That's not the right answer; your answer is too high.</p>",
+            ),
+            (200, "<p>That's the right answer!</p>"),
+        ]);
+        let client = UreqClient::new();
+        let mut website = Website::new(&client, "abc".to_string())
+            .with_base_url(&base_url)
+            .with_min_interval(Duration::ZERO);
+
+        let reply = submit_answer(&mut website, &data, "day01", 1, "42").unwrap();
+        assert_eq!(reply, Reply::Verdict(Verdict::TooHigh));
+        assert!(submit_answer(&mut website, &data, "day01", 1, "43").is_err());
+        let reply = submit_answer(&mut website, &data, "day01", 1, "11").unwrap();
+        assert_eq!(reply, Reply::Verdict(Verdict::Correct));
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].head.starts_with("POST /2024/day/1/answer "));
+        assert_eq!(requests[0].body, "level=1&answer=42");
+        let history = fs::read_to_string(root.join("day01/submissions")).unwrap();
+        assert_eq!(history, "part1 too-high: 42\npart1 correct: 11\n");
+    }
+}
@@ -0,0 +1,380 @@
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{Frame, Terminal};
+use shared::log::{self, Verbosity};
+use shared::{DataDir, InputProvider, Params};
+use std::collections::HashMap;
+use std::io::{self, Stdout};
+use std::panic;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use std::{fmt::Write, fs};
+
+use crate::register_days::DayMap;
+use crate::runner::{self, DayReport};
+
+type Output = Arc<Mutex<Vec<String>>>;
+
+/// Browses the registered days, runs them and shows their answers and logged output,
+/// until `q` is pressed.
+pub fn run_tui(data: &DataDir, days_map: &DayMap, overrides: &Params) -> shared::Result<()> {
+    let output = Output::default();
+    let sink = output.clone();
+    log::set_sink(Some(Box::new(move |text| {
+        let mut output = sink.lock().unwrap_or_else(|err| err.into_inner());
+        output.extend(text.lines().map(str::to_string));
+    })));
+
+    // Panics of the solutions end up in their reports, printing them would garble the screen
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        if thread::current().name() == Some("main") {
+            let _ = restore_terminal();
+            eprintln!("{info}");
+        }
+    }));
+
+    let mut terminal = init_terminal()?;
+    let res = App::new(data, days_map, overrides, output).run(&mut terminal);
+    restore_terminal()?;
+
+    let _ = panic::take_hook();
+    panic::set_hook(default_hook);
+    log::set_sink(None);
+    res
+}
+
+fn init_terminal() -> io::Result<Terminal<CrosstermBackend<Stdout>>> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    Terminal::new(CrosstermBackend::new(io::stdout()))
+}
+
+fn restore_terminal() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen)
+}
+
+struct Run {
+    day: &'static str,
+    report: Receiver<DayReport>,
+}
+
+struct App<'a> {
+    data: &'a DataDir,
+    days_map: &'a DayMap,
+    overrides: &'a Params,
+    days: Vec<&'static str>,
+    list: ListState,
+    part: Option<u8>,
+    inputs: Vec<String>,
+    input: usize,
+    reports: HashMap<&'static str, DayReport>,
+    output: Output,
+    output_title: String,
+    scroll: u16,
+    running: Option<Run>,
+    quit: bool,
+}
+
+impl<'a> App<'a> {
+    fn new(data: &'a DataDir, days_map: &'a DayMap, overrides: &'a Params, output: Output) -> Self {
+        let mut app = App {
+            data,
+            days_map,
+            overrides,
+            days: days_map.keys().copied().collect(),
+            list: ListState::default().with_selected(Some(0)),
+            part: None,
+            inputs: vec![],
+            input: 0,
+            reports: HashMap::new(),
+            output,
+            output_title: "Output".to_string(),
+            scroll: 0,
+            running: None,
+            quit: false,
+        };
+        app.select(0);
+        app
+    }
+
+    fn run(mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> shared::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            self.collect_report();
+
+            if event::poll(Duration::from_millis(100))? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        self.on_key(key.code);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn selected_day(&self) -> &'static str {
+        self.days[self.list.selected().unwrap_or(0)]
+    }
+
+    fn select(&mut self, idx: usize) {
+        if self.days.is_empty() {
+            return;
+        }
+        self.list.select(Some(idx.min(self.days.len() - 1)));
+        self.inputs = input_files(self.data, self.selected_day());
+        self.input = 0;
+    }
+
+    fn on_key(&mut self, key: KeyCode) {
+        let selected = self.list.selected().unwrap_or(0);
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => self.select(selected.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => self.select(selected + 1),
+            KeyCode::Enter => self.start_run(),
+            KeyCode::Char('p') => self.part = next_part(self.part),
+            KeyCode::Char('i') if !self.inputs.is_empty() => {
+                self.input = (self.input + 1) % self.inputs.len();
+            }
+            KeyCode::Char('v') => log::set_verbosity(next_verbosity(log::verbosity())),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::Home => self.scroll = 0,
+            _ => {}
+        }
+    }
+
+    /// Runs the selected day on a separate thread, so the screen keeps updating meanwhile.
+    fn start_run(&mut self) {
+        if self.running.is_some() || self.days.is_empty() {
+            return;
+        }
+
+        let day = self.selected_day();
+        let solution = self.days_map[day];
+        let input_name = self.inputs.get(self.input).cloned();
+        let input = match &input_name {
+            Some(name) => self.data.read(day, name),
+            None => runner::read_input(self.data, day, None),
+        };
+        let params = runner::read_params(self.data, day, self.overrides);
+        let part = self.part;
+
+        self.output
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clear();
+        self.output_title = format!("{day} on {}", input_name.as_deref().unwrap_or("input"));
+        self.scroll = 0;
+
+        let (sender, report) = mpsc::channel();
+        let spawned = thread::Builder::new()
+            .name(format!("{day}-run"))
+            .spawn(move || {
+                let report = runner::run_day(day, solution, input, params, part, |_, _| ());
+                let _ = sender.send(report);
+            });
+
+        match spawned {
+            Ok(_) => self.running = Some(Run { day, report }),
+            Err(err) => self.log(format!("error: cannot start {day}: {err}")),
+        }
+    }
+
+    fn collect_report(&mut self) {
+        let Some(run) = &self.running else {
+            return;
+        };
+        let Ok(report) = run.report.try_recv() else {
+            return;
+        };
+
+        let day = run.day;
+        self.running = None;
+        self.log(String::new());
+        for line in describe_report(&report).lines() {
+            self.log(line.to_string());
+        }
+        self.reports.insert(day, report);
+    }
+
+    fn log(&self, line: String) {
+        self.output
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(line);
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, help] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+        let [days, right] =
+            Layout::horizontal([Constraint::Length(44), Constraint::Min(20)]).areas(main);
+        let [settings, output] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(3)]).areas(right);
+
+        let items: Vec<ListItem> = self
+            .days
+            .iter()
+            .map(|&day| ListItem::new(day_line(day, self.reports.get(day))))
+            .collect();
+        let list = List::new(items)
+            .block(Block::bordered().title("Days"))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, days, &mut self.list);
+
+        let status = match &self.running {
+            Some(run) => format!("   running {}...", run.day),
+            None => String::new(),
+        };
+        let settings_line = format!(
+            "Part: {}   Input: {}   Verbosity: {:?}{status}",
+            self.part
+                .map_or("both".to_string(), |part| part.to_string()),
+            self.inputs
+                .get(self.input)
+                .map_or("input (missing)", String::as_str),
+            log::verbosity(),
+        );
+        frame.render_widget(
+            Paragraph::new(settings_line).block(Block::bordered().title("Settings")),
+            settings,
+        );
+
+        let lines: Vec<Line> = self
+            .output
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .map(|line| Line::raw(line.clone()))
+            .collect();
+        let max_scroll = (lines.len() as u16).saturating_sub(output.height.saturating_sub(2));
+        self.scroll = self.scroll.min(max_scroll);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(Block::bordered().title(self.output_title.as_str()))
+                .scroll((self.scroll, 0)),
+            output,
+        );
+
+        frame.render_widget(
+            Paragraph::new(
+                "↑/↓ select  Enter run  p part  i input  v verbosity  PgUp/PgDn scroll  q quit",
+            )
+            .style(Style::default().fg(Color::DarkGray)),
+            help,
+        );
+    }
+}
+
+/// The input files of a day, its puzzle `input` first and then the samples by name.
+fn input_files(data: &DataDir, day: &str) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(data.root().join(day))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name == "input" || name.ends_with("_input") || name.contains("_input_"))
+        .collect();
+    names.sort_by_key(|name| (name != "input", name.clone()));
+    names
+}
+
+fn next_part(part: Option<u8>) -> Option<u8> {
+    match part {
+        None => Some(1),
+        Some(1) => Some(2),
+        _ => None,
+    }
+}
+
+fn next_verbosity(verbosity: Verbosity) -> Verbosity {
+    match verbosity {
+        Verbosity::Quiet | Verbosity::Normal => Verbosity::Verbose,
+        Verbosity::Verbose => Verbosity::Trace,
+        Verbosity::Trace => Verbosity::Normal,
+    }
+}
+
+fn day_line(day: &str, report: Option<&DayReport>) -> String {
+    let Some(report) = report else {
+        return day.to_string();
+    };
+    if report.error.is_some() {
+        return format!("{day}  failed");
+    }
+
+    let answer = |part: &Option<runner::PartReport>| {
+        part.as_ref()
+            .map_or("-".to_string(), |part| part.answer.clone())
+    };
+    format!(
+        "{day}  {:<14} {:<14} {:.1?}",
+        answer(&report.part1),
+        answer(&report.part2),
+        report.elapsed
+    )
+}
+
+fn describe_report(report: &DayReport) -> String {
+    let mut text = String::new();
+    for (part, res) in [(1, &report.part1), (2, &report.part2)] {
+        if let Some(res) = res {
+            let _ = writeln!(text, "Part {part}: {} ({:.2?})", res.answer, res.elapsed);
+        }
+    }
+    if let Some(err) = &report.error {
+        let _ = writeln!(text, "error: {err}");
+    }
+    let _ = writeln!(text, "Total: {:.2?}", report.elapsed);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puzzle_input_listed_first() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().to_path_buf();
+        fs::create_dir_all(root.join("day03/src")).unwrap();
+        for name in [
+            "sample_input_2",
+            "input",
+            "sample_input_1",
+            "answers",
+            "small_input",
+        ] {
+            fs::write(root.join("day03").join(name), "").unwrap();
+        }
+
+        let names = input_files(&DataDir::new(&root), "day03");
+        assert_eq!(
+            names,
+            ["input", "sample_input_1", "sample_input_2", "small_input"]
+        );
+        assert!(input_files(&DataDir::new(&root), "day04").is_empty());
+    }
+
+    #[test]
+    fn cycling_settings() {
+        assert_eq!(next_part(None), Some(1));
+        assert_eq!(next_part(Some(2)), None);
+        assert_eq!(next_verbosity(Verbosity::Trace), Verbosity::Normal);
+    }
+}
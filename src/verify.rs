@@ -0,0 +1,206 @@
+use shared::{Error, InputProvider, Params};
+use std::process::ExitCode;
+
+use crate::register_days::DayMap;
+use crate::runner;
+
+/// Known answers of a day, stored next to its input as `<day>/answers`:
+///
+/// ```text
+/// part1: 11
+/// part2: 31
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+pub fn load_answers(data: &dyn InputProvider, day: &str) -> shared::Result<Option<Answers>> {
+    data.read_optional(day, "answers")?
+        .map(|contents| parse_answers(&contents))
+        .transpose()
+}
+
+fn parse_answers(contents: &str) -> shared::Result<Answers> {
+    let mut answers = Answers::default();
+
+    for (idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = |msg: &str| Error::parse(idx + 1, 1, msg);
+
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| invalid("expected `part1: <answer>` or `part2: <answer>`"))?;
+        let slot = match key.trim() {
+            "part1" => &mut answers.part1,
+            "part2" => &mut answers.part2,
+            other => return Err(invalid(&format!("unknown key `{other}`"))),
+        };
+        *slot = Some(value.trim().to_string());
+    }
+
+    Ok(answers)
+}
+
+/// Runs every given day that has known answers and prints a diff for each mismatch.
+pub fn verify_days(
+    data: &dyn InputProvider,
+    days_map: &DayMap,
+    days: &[&'static str],
+    params: &Params,
+) -> ExitCode {
+    let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+
+    for &day in days {
+        let answers = match load_answers(data, day) {
+            Ok(Some(answers)) => answers,
+            Ok(None) => {
+                println!("{day}: skipped (no answers file)");
+                skipped += 1;
+                continue;
+            }
+            Err(err) => {
+                println!("{day}: FAILED ({day}/answers: {err})");
+                failed += 1;
+                continue;
+            }
+        };
+
+        let part = match (&answers.part1, &answers.part2) {
+            (None, None) => {
+                println!("{day}: skipped (no answers in {day}/answers)");
+                skipped += 1;
+                continue;
+            }
+            (Some(_), None) => Some(1),
+            (None, Some(_)) => Some(2),
+            (Some(_), Some(_)) => None,
+        };
+
+        let input = runner::read_input(data, day, None);
+        let params = runner::read_params(data, day, params);
+        let report = runner::run_day(day, days_map[day], input, params, part, |_, _| ());
+        if let Some(err) = report.error {
+            println!("{day}: FAILED ({err})");
+            failed += 1;
+            continue;
+        }
+
+        let mismatches = mismatches(&answers, &report);
+        if mismatches.is_empty() {
+            println!("{day}: ok");
+            passed += 1;
+            continue;
+        }
+
+        println!("{day}: FAILED");
+        println!("--- {day}/answers");
+        println!("+++ {day} (actual)");
+        for (part, expected, actual) in mismatches {
+            println!("@@ part {part} @@");
+            println!("-{expected}");
+            println!("+{}", actual.unwrap_or("<no answer>"));
+        }
+        failed += 1;
+    }
+
+    println!("{passed} passed, {failed} failed, {skipped} skipped");
+
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// The parts whose expected answer differs from the actual one, along with both. A part with an
+/// expected answer but no actual one counts as a mismatch, so a day only passes when every
+/// expected answer was compared.
+fn mismatches<'a>(
+    answers: &'a Answers,
+    report: &'a runner::DayReport,
+) -> Vec<(u8, &'a str, Option<&'a str>)> {
+    [
+        (1, &answers.part1, &report.part1),
+        (2, &answers.part2, &report.part2),
+    ]
+    .into_iter()
+    .filter_map(|(part, expected, actual)| {
+        let expected = expected.as_deref()?;
+        let actual = actual.as_ref().map(|actual| actual.answer.as_str());
+        (actual != Some(expected)).then_some((part, expected, actual))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn report(part1: Option<&str>, part2: Option<&str>) -> runner::DayReport {
+        let part = |answer: Option<&str>| {
+            answer.map(|answer| runner::PartReport {
+                answer: answer.to_string(),
+                elapsed: Duration::ZERO,
+            })
+        };
+        runner::DayReport {
+            day: "day01",
+            part1: part(part1),
+            part2: part(part2),
+            elapsed: Duration::ZERO,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn missing_answers_are_mismatches() {
+        let answers = parse_answers("part1: 11\npart2: 31\n").unwrap();
+        assert!(mismatches(&answers, &report(Some("11"), Some("31"))).is_empty());
+        assert_eq!(
+            mismatches(&answers, &report(Some("11"), Some("32"))),
+            [(2, "31", Some("32"))]
+        );
+        assert_eq!(
+            mismatches(&answers, &report(Some("11"), None)),
+            [(2, "31", None)]
+        );
+    }
+
+    #[test]
+    fn parse_both_parts() {
+        let answers = parse_answers("part1: 11\npart2: 31\n").unwrap();
+        assert_eq!(
+            answers,
+            Answers {
+                part1: Some("11".to_string()),
+                part2: Some("31".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_single_part_with_comments() {
+        let answers = parse_answers("# day 25 has no second part\n\npart1:  3 \n").unwrap();
+        assert_eq!(answers.part1, Some("3".to_string()));
+        assert_eq!(answers.part2, None);
+    }
+
+    #[test]
+    fn parse_answer_with_colons() {
+        let answers = parse_answers("part2: a:b,c").unwrap();
+        assert_eq!(answers.part2, Some("a:b,c".to_string()));
+    }
+
+    #[test]
+    fn parse_invalid_lines() {
+        assert!(parse_answers("11").is_err());
+        assert!(parse_answers("part3: 11").is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use shared::{DataDir, Error};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use std::{fs, io};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The answer, or the error, of each part of a run.
+type Answers = BTreeMap<u8, Result<String, String>>;
+
+/// Rebuilds the day whenever a file in its directory changes, runs its sample tests and its
+/// puzzle input, and shows how the answers changed since the previous run. Runs until killed.
+pub fn watch_day(data: &DataDir, day: &str, release: bool) -> shared::Result<ExitCode> {
+    let root = data.root();
+    let dir = root.join(day);
+    let mut previous = Answers::new();
+    let mut files = snapshot(&dir)?;
+
+    loop {
+        println!("[{day}] building and testing...");
+        match run_tests(root, day, release) {
+            Ok(true) => println!("[{day}] sample tests passed"),
+            Ok(false) => println!("[{day}] sample tests FAILED"),
+            Err(err) => println!("[{day}] cannot run the tests: {err}"),
+        }
+
+        match run_solution(root, day, release) {
+            Ok(answers) => {
+                diff_answers(&previous, &answers)
+                    .iter()
+                    .for_each(|line| println!("[{day}] {line}"));
+                previous = answers;
+            }
+            Err(err) => println!("[{day}] cannot run the solution: {err}"),
+        }
+
+        println!("[{day}] waiting for changes in {}", dir.display());
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            // Editors saving through a rename can make a file vanish mid-scan, try again next time
+            let current = match snapshot(&dir) {
+                Ok(current) => current,
+                Err(err) => {
+                    shared::info!("[{day}] cannot scan {}: {err}", dir.display());
+                    continue;
+                }
+            };
+            if current != files {
+                files = current;
+                break;
+            }
+        }
+    }
+}
+
+/// Modification times of every file under `dir`, build output excluded.
+fn snapshot(dir: &Path) -> shared::Result<BTreeMap<PathBuf, SystemTime>> {
+    let mut files = BTreeMap::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                if entry.file_name() != "target" {
+                    dirs.push(path);
+                }
+            } else {
+                files.insert(path, entry.metadata()?.modified()?);
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn cargo(root: &Path, subcommand: &str, release: bool) -> Command {
+    let mut command = Command::new(env!("CARGO"));
+    command.current_dir(root).arg(subcommand).arg("--quiet");
+    if release {
+        command.arg("--release");
+    }
+    command
+}
+
+fn run_tests(root: &Path, day: &str, release: bool) -> io::Result<bool> {
+    let status = cargo(root, "test", release)
+        .args(["--package", day])
+        .stdout(Stdio::null())
+        .status()?;
+    Ok(status.success())
+}
+
+/// Runs the freshly built binary on the day's puzzle input and collects its JSON report.
+fn run_solution(root: &Path, day: &str, release: bool) -> shared::Result<Answers> {
+    let output = cargo(root, "run", release)
+        .args([
+            "--bin",
+            env!("CARGO_PKG_NAME"),
+            "--",
+            day,
+            "--format",
+            "json",
+        ])
+        .arg("--data-dir")
+        .arg(root)
+        .stderr(Stdio::inherit())
+        .output()?;
+    reported_answers(output.status, &output.stdout)
+}
+
+/// The answers of a run, which has to report at least one part. A day that fails still reports
+/// its parts along with the error, a build that fails doesn't report anything.
+fn reported_answers(status: ExitStatus, stdout: &[u8]) -> shared::Result<Answers> {
+    let answers = parse_json_answers(&String::from_utf8_lossy(stdout))?;
+    if answers.is_empty() {
+        let message = if status.success() {
+            "No answers reported".to_string()
+        } else {
+            format!("No answers reported, the run failed with {status}")
+        };
+        return Err(Error::InvalidInput(message));
+    }
+    Ok(answers)
+}
+
+fn parse_json_answers(output: &str) -> shared::Result<Answers> {
+    let invalid = |line: &str| Error::InvalidInput(format!("Unexpected report line `{line}`"));
+
+    let mut answers = Answers::new();
+    for line in output.lines().filter(|line| !line.trim().is_empty()) {
+        let record: serde_json::Value = serde_json::from_str(line).map_err(|_| invalid(line))?;
+        let part = record["part"].as_u64().ok_or_else(|| invalid(line))? as u8;
+        let answer = match (record["answer"].as_str(), record["error"].as_str()) {
+            (Some(answer), _) => Ok(answer.to_string()),
+            (None, error) => Err(error.unwrap_or("no answer").to_string()),
+        };
+        answers.insert(part, answer);
+    }
+    Ok(answers)
+}
+
+fn diff_answers(previous: &Answers, current: &Answers) -> Vec<String> {
+    current
+        .iter()
+        .map(|(part, answer)| {
+            let now = match answer {
+                Ok(answer) => answer.clone(),
+                Err(err) => format!("error: {err}"),
+            };
+            match previous.get(part) {
+                Some(before) if before == answer => format!("part{part}: {now} (unchanged)"),
+                Some(Ok(before)) => format!("part{part}: {before} -> {now}"),
+                Some(Err(_)) | None => format!("part{part}: {now}"),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_from_json_report() {
+        let output = concat!(
+            r#"{"day":"day01","part":1,"answer":"11","duration_ns":5,"error":null}"#,
+            "\n",
+            r#"{"day":"day01","part":2,"answer":null,"duration_ns":5,"error":"panicked: oops"}"#,
+        );
+        let answers = parse_json_answers(output).unwrap();
+        assert_eq!(answers[&1], Ok("11".to_string()));
+        assert_eq!(answers[&2], Err("panicked: oops".to_string()));
+        assert!(parse_json_answers("Part one result: 11").is_err());
+        assert!(reported_answers(ExitStatus::default(), b"").is_err());
+        assert!(reported_answers(ExitStatus::default(), output.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn answer_changes() {
+        let previous = Answers::from([(1, Ok("11".to_string())), (2, Ok("31".to_string()))]);
+        let current = Answers::from([(1, Ok("11".to_string())), (2, Ok("32".to_string()))]);
+        assert_eq!(
+            diff_answers(&previous, &current),
+            ["part1: 11 (unchanged)", "part2: 31 -> 32"]
+        );
+        assert_eq!(diff_answers(&Answers::new(), &current)[0], "part1: 11");
+    }
+
+    #[test]
+    fn snapshot_sees_changes() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().to_path_buf();
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "").unwrap();
+        fs::write(dir.join("target/ignored"), "").unwrap();
+
+        let before = snapshot(&dir).unwrap();
+        assert_eq!(before.len(), 1);
+        fs::write(dir.join("input"), "1").unwrap();
+        assert_ne!(snapshot(&dir).unwrap(), before);
+    }
+}
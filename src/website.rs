@@ -0,0 +1,276 @@
+use shared::Error;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+use std::{env, fs, io};
+
+pub const BASE_URL: &str = "https://adventofcode.com/2024";
+const USER_AGENT: &str = "github.com/dimbata23/aoc-2024 by Alexander Dimitrov";
+const SESSION_ENV: &str = "AOC_SESSION";
+const SESSION_FILE: &str = ".aoc-session";
+/// Keeps automated requests well below what the website tolerates.
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// The HTTP requests made to the website, so tests can swap in a client for a local server.
+pub trait HttpClient {
+    fn get(&self, url: &str, session: &str) -> shared::Result<Response>;
+
+    fn post_form(
+        &self,
+        url: &str,
+        session: &str,
+        form: &[(&str, &str)],
+    ) -> shared::Result<Response>;
+}
+
+pub struct UreqClient {
+    agent: ureq::Agent,
+}
+
+impl UreqClient {
+    pub fn new() -> Self {
+        UreqClient {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str, session: &str) -> shared::Result<Response> {
+        let res = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={session}"))
+            .call();
+        into_response(url, res)
+    }
+
+    fn post_form(
+        &self,
+        url: &str,
+        session: &str,
+        form: &[(&str, &str)],
+    ) -> shared::Result<Response> {
+        let res = self
+            .agent
+            .post(url)
+            .set("Cookie", &format!("session={session}"))
+            .send_form(form);
+        into_response(url, res)
+    }
+}
+
+fn into_response(url: &str, res: Result<ureq::Response, ureq::Error>) -> shared::Result<Response> {
+    let response = match res {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+        Err(err) => return Err(Error::Remote(format!("{url}: {err}"))),
+    };
+
+    Ok(Response {
+        status: response.status(),
+        body: response.into_string()?,
+    })
+}
+
+/// The session token of the logged in user, taken from `AOC_SESSION` or the `.aoc-session` file in `root`.
+pub fn session_token(root: &Path) -> shared::Result<String> {
+    if let Ok(token) = env::var(SESSION_ENV) {
+        if !token.trim().is_empty() {
+            return Ok(token.trim().to_string());
+        }
+    }
+
+    match fs::read_to_string(root.join(SESSION_FILE)) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+        _ => Err(Error::InvalidArgument(format!(
+            "No session token, set `{SESSION_ENV}` or put it in `{SESSION_FILE}`"
+        ))),
+    }
+}
+
+/// A logged in session on the website that spaces out consecutive requests.
+pub struct Website<'a> {
+    client: &'a dyn HttpClient,
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl<'a> Website<'a> {
+    pub fn new(client: &'a dyn HttpClient, session: String) -> Self {
+        Website {
+            client,
+            base_url: BASE_URL.to_string(),
+            session,
+            min_interval: MIN_REQUEST_INTERVAL,
+            last_request: None,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    #[cfg(test)]
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// `GET`s `path` relative to the year's URL and turns the website's refusals into errors.
+    pub fn get(&mut self, path: &str) -> shared::Result<String> {
+        self.throttle();
+        let url = format!("{}/{path}", self.base_url);
+        let response = self.client.get(&url, &self.session)?;
+        check_status(&url, response)
+    }
+
+    /// `POST`s `form` to `path` relative to the year's URL, see [`Website::get`].
+    pub fn post(&mut self, path: &str, form: &[(&str, &str)]) -> shared::Result<String> {
+        self.throttle();
+        let url = format!("{}/{path}", self.base_url);
+        let response = self.client.post_form(&url, &self.session, form)?;
+        check_status(&url, response)
+    }
+
+    fn throttle(&mut self) {
+        if let Some(last) = self.last_request {
+            thread::sleep(self.min_interval.saturating_sub(last.elapsed()));
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+fn check_status(url: &str, response: Response) -> shared::Result<String> {
+    let reason = match response.status {
+        200..=299 => return Ok(response.body),
+        400 | 401 => "the session token was rejected, log in again and update it",
+        404 => "not found, the puzzle may not be unlocked yet",
+        429 => "too many requests, try again later",
+        _ => "unexpected response",
+    };
+    Err(Error::Remote(format!(
+        "{url}: {} ({reason})",
+        response.status
+    )))
+}
+
+/// The day of the month of a day crate's name, e.g. 7 for `day07`.
+pub fn day_number(day: &str) -> shared::Result<u8> {
+    day.strip_prefix("day")
+        .and_then(|num| num.parse().ok())
+        .filter(|num| (1..=25).contains(num))
+        .ok_or_else(|| Error::DayNotFound(day.to_string()))
+}
+
+#[cfg(test)]
+pub mod stand_in {
+    //! A local stand-in for the website, answering each connection with the next canned response.
+
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    pub struct Request {
+        pub head: String,
+        pub body: String,
+    }
+
+    /// Serves `responses` in order on a random port, returns its base URL and the received requests.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<Request>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/2024", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut head = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+
+                let len = head
+                    .lines()
+                    .filter_map(|line| line.split_once(':'))
+                    .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                    .map_or(0, |(_, len)| len.trim().parse().unwrap());
+                let mut body_bytes = vec![0; len];
+                reader.read_exact(&mut body_bytes).unwrap();
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+
+                requests.push(Request {
+                    head,
+                    body: String::from_utf8(body_bytes).unwrap(),
+                });
+            }
+            requests
+        });
+
+        (base_url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sends_the_session_cookie() {
+        let (base_url, server) = stand_in::serve(vec![(200, "1\n2\n")]);
+        let client = UreqClient::new();
+        let mut website = Website::new(&client, "abc".to_string()).with_base_url(&base_url);
+
+        assert_eq!(website.get("day/1/input").unwrap(), "1\n2\n");
+        let requests = server.join().unwrap();
+        assert!(requests[0].head.starts_with("GET /2024/day/1/input "));
+        assert!(requests[0].head.contains("session=abc"));
+    }
+
+    #[test]
+    fn refusals_are_errors() {
+        let (base_url, server) = stand_in::serve(vec![(400, "log in"), (404, "")]);
+        let client = UreqClient::new();
+        let mut website = Website::new(&client, "abc".to_string())
+            .with_base_url(&base_url)
+            .with_min_interval(Duration::ZERO);
+
+        let err = website.get("day/1/input").unwrap_err();
+        assert!(err.to_string().contains("session token was rejected"));
+        let err = website.get("day/25/input").unwrap_err();
+        assert!(err.to_string().contains("not be unlocked yet"));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn day_numbers() {
+        assert_eq!(day_number("day07").unwrap(), 7);
+        assert!(day_number("day26").is_err());
+        assert!(day_number("7").is_err());
+    }
+}